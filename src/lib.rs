@@ -1,4 +1,3 @@
-#![feature(const_fn_fn_ptr_basics)]
 #![no_std]
 
 pub mod v1;
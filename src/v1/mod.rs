@@ -51,6 +51,12 @@ pub struct StivaleHeader {
     entry_point: StivaleHeaderEntryPoint,
 }
 
+impl Default for StivaleHeader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StivaleHeader {
     pub fn new() -> Self {
         Self {
@@ -119,6 +125,8 @@ pub struct StivaleModule {
     /// ASCII 0-terminated string passed to the module as specified in
     /// the config file.
     pub string: [u8; 128],
+    /// Address of the next module in the list, or zero if this is the last module.
+    pub next: u64,
 }
 
 impl StivaleModule {
@@ -139,10 +147,10 @@ impl StivaleModule {
 /// Iterator over all the modules that were loaded.
 #[derive(Clone)]
 pub struct StivaleModuleIter<'a> {
-    /// A reference to the stivale structure.
-    sref: &'a StivaleStruct,
-    /// The index of the module entry that we are about to index.
+    /// Address of the module that we are about to yield.
     current: u64,
+    /// The amount of modules that are left to yield according to the stivale structure.
+    remaining: u64,
     phantom: PhantomData<&'a StivaleModule>,
 }
 
@@ -150,14 +158,15 @@ impl<'a> Iterator for StivaleModuleIter<'a> {
     type Item = &'a StivaleModule;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current < self.sref.module_len {
-            let entry = &self.sref.modules_as_slice()[self.current as usize];
-            self.current += 1;
-
-            Some(entry)
-        } else {
-            None
+        if self.current == 0 || self.remaining == 0 {
+            return None;
         }
+
+        let module = unsafe { &*(self.current as *const StivaleModule) };
+        self.current = module.next;
+        self.remaining -= 1;
+
+        Some(module)
     }
 }
 
@@ -246,8 +255,8 @@ impl<'a> Iterator for StivaleMemoryMapIter<'a> {
 pub struct StivaleStruct {
     /// Address of the null-terminated command line.
     pub command_line: u64,
-    /// Pointer to the memory map array.
-    pub memory_map_array: [StivaleMemoryMapEntry; 0],
+    /// Address of the memory map array.
+    pub memory_map_addr: u64,
     /// Length of the memory map entries.
     pub memory_map_len: u64,
 
@@ -268,13 +277,15 @@ pub struct StivaleStruct {
     /// The length of modules that the stivale bootloader loaded according to the
     /// config.
     pub module_len: u64,
-    /// Pointer to the modules array.
-    pub modules: [StivaleModule; 0],
+    /// Address of the first module of the linked list of modules.
+    pub modules: u64,
 
     /// UNIX epoch at boot, which is read from system RTC.
     pub unix_epoch: u64,
     pub flags: u64,
 
+    /// The memory model of the framebuffer, 1 is RGB.
+    pub fb_memory_model: u8,
     /// Size of the red mask in RGB.
     pub red_mask_size: u8,
     /// Shift of the red mask in RGB.
@@ -296,36 +307,36 @@ pub struct StivaleStruct {
 }
 
 impl StivaleStruct {
-    /// Return's the modules array pointer as a rust slice.
-    pub fn modules_as_slice(&self) -> &[StivaleModule] {
-        unsafe { core::slice::from_raw_parts(self.modules.as_ptr(), self.module_len as usize) }
-    }
-
-    /// Returns an iterator over all the modules that were loaded.
-    pub fn modules_iter(&self) -> StivaleModuleIter {
+    /// Returns an iterator over all the modules that were loaded, following the linked list
+    /// of modules.
+    pub fn modules_iter(&self) -> StivaleModuleIter<'_> {
         StivaleModuleIter {
-            sref: self,
-            current: 0,
-            phantom: PhantomData::default(),
+            current: self.modules,
+            remaining: self.module_len,
+            phantom: PhantomData,
         }
     }
 
     /// Return's memory map entries pointer as a rust slice.
     pub fn memory_map_as_slice(&self) -> &[StivaleMemoryMapEntry] {
+        if self.memory_map_addr == 0 {
+            return &[];
+        }
+
         unsafe {
             core::slice::from_raw_parts(
-                self.memory_map_array.as_ptr(),
+                self.memory_map_addr as *const StivaleMemoryMapEntry,
                 self.memory_map_len as usize,
             )
         }
     }
 
     /// Returns an iterator over all the memory regions.
-    pub fn memory_map_iter(&self) -> StivaleMemoryMapIter {
+    pub fn memory_map_iter(&self) -> StivaleMemoryMapIter<'_> {
         StivaleMemoryMapIter {
             sref: self,
             current: 0x00,
-            phantom: PhantomData::default(),
+            phantom: PhantomData,
         }
     }
 }
@@ -0,0 +1,167 @@
+//! This module contains helpers to read the ACPI tables located through the stivale2 RSDP tag.
+
+use super::tag::StivaleRsdpTag;
+
+/// The ACPI Root System Description Pointer structure.
+#[repr(C, packed)]
+pub struct Rsdp {
+    signature: [u8; 8],
+    checksum: u8,
+    oem_id: [u8; 6],
+    revision: u8,
+    rsdt_address: u32,
+    // The following fields are only valid if revision >= 2.
+    length: u32,
+    xsdt_address: u64,
+    extended_checksum: u8,
+    _reserved: [u8; 3],
+}
+
+/// The header shared by all the ACPI System Description Tables.
+#[repr(C, packed)]
+pub struct SdtHeader {
+    signature: [u8; 4],
+    length: u32,
+    revision: u8,
+    checksum: u8,
+    oem_id: [u8; 6],
+    oem_table_id: [u8; 8],
+    oem_revision: u32,
+    creator_id: u32,
+    creator_revision: u32,
+}
+
+impl SdtHeader {
+    /// Returns the signature of the table, such as `"APIC"` for the MADT.
+    pub fn signature(&self) -> [u8; 4] {
+        self.signature
+    }
+
+    /// Returns the length of the table in bytes, including the header.
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+}
+
+impl Rsdp {
+    /// The length of the ACPI 1.0 part of the structure, which `checksum` applies to.
+    const V1_LENGTH: usize = 20;
+
+    /// Returns the revision of the structure. 0 means ACPI 1.0 and 2 means ACPI 2.0 or newer.
+    pub fn revision(&self) -> u8 {
+        self.revision
+    }
+
+    /// Returns the signature, which must be `"RSD PTR "`.
+    pub fn signature(&self) -> [u8; 8] {
+        self.signature
+    }
+
+    /// Returns the OEM ID.
+    pub fn oem_id(&self) -> [u8; 6] {
+        self.oem_id
+    }
+
+    /// Returns the 32-bit physical address of the RSDT.
+    pub fn rsdt_address(&self) -> u32 {
+        self.rsdt_address
+    }
+
+    /// Returns the 64-bit physical address of the XSDT, which is only present if the revision
+    /// is 2 or newer.
+    pub fn xsdt_address(&self) -> Option<u64> {
+        if self.revision >= 2 {
+            Some(self.xsdt_address)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the byte sum of the ACPI 1.0 part of the structure is zero.
+    pub fn checksum_valid(&self) -> bool {
+        let bytes = unsafe {
+            core::slice::from_raw_parts(self as *const Self as *const u8, Self::V1_LENGTH)
+        };
+
+        bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) == 0
+    }
+
+    /// Returns an iterator over the physical addresses of the tables referenced by the XSDT, or
+    /// by the RSDT if there is no XSDT. Nothing is yielded if the signature of the root table is
+    /// wrong or its length is smaller than its header.
+    ///
+    /// ## Safety
+    /// The root table and its entries are read at the physical address stored in this structure,
+    /// so the caller must ensure that they are mapped there, for example by identity mapping the
+    /// ACPI memory, for as long as the iterator is used.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::Rsdp;
+    /// #[repr(C, packed)]
+    /// struct Xsdt {
+    ///     signature: [u8; 4],
+    ///     length: u32,
+    ///     _header: [u8; 28],
+    ///     entries: [u64; 2],
+    /// }
+    ///
+    /// let xsdt = Xsdt { signature: *b"XSDT", length: 36 + 16, _header: [0; 28], entries: [0x1000, 0x2000] };
+    ///
+    /// let mut rsdp = [0u8; 36];
+    /// rsdp[..8].copy_from_slice(b"RSD PTR ");
+    /// rsdp[15] = 2;
+    /// rsdp[24..32].copy_from_slice(&(&xsdt as *const Xsdt as u64).to_ne_bytes());
+    /// let rsdp = unsafe { &*(rsdp.as_ptr() as *const Rsdp) };
+    ///
+    /// let mut tables = unsafe { rsdp.sdt_pointers() };
+    /// assert_eq!(tables.next(), Some(0x1000));
+    /// assert_eq!(tables.next(), Some(0x2000));
+    /// assert_eq!(tables.next(), None);
+    /// ```
+    pub unsafe fn sdt_pointers(&self) -> impl Iterator<Item = u64> {
+        let (address, signature, entry_size) = match self.xsdt_address() {
+            Some(address) if address != 0 => (address, b"XSDT", 8),
+            _ => (self.rsdt_address() as u64, b"RSDT", 4),
+        };
+
+        let header_length = core::mem::size_of::<SdtHeader>();
+        let mut count = 0;
+
+        if address != 0 {
+            let header = &*(address as *const SdtHeader);
+            let length = header.length() as usize;
+
+            if &header.signature() == signature && length >= header_length {
+                count = (length - header_length) / entry_size;
+            }
+        }
+
+        (0..count).map(move |index| {
+            let entry = (address as usize + header_length + index * entry_size) as *const u8;
+
+            unsafe {
+                if entry_size == 8 {
+                    (entry as *const u64).read_unaligned()
+                } else {
+                    (entry as *const u32).read_unaligned() as u64
+                }
+            }
+        })
+    }
+}
+
+impl StivaleRsdpTag {
+    /// Returns the ACPI RSDP structure pointed to by this tag, or `None` if the pointer is null.
+    ///
+    /// ## Safety
+    /// The RSDP is read at the address reported by the bootloader, so the caller must ensure that
+    /// it is mapped there, for example by identity mapping the firmware memory.
+    pub unsafe fn as_rsdp(&self) -> Option<&Rsdp> {
+        if self.rsdp == 0 {
+            None
+        } else {
+            Some(&*(self.rsdp as *const Rsdp))
+        }
+    }
+}
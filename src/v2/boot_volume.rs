@@ -0,0 +1,34 @@
+//! This module contains the helpers of the stivale2 boot volume tag.
+
+use super::tag::{StivaleBootVolumeTag, StivaleBootVolumeTagFlags};
+use super::utils::Guid;
+
+impl StivaleBootVolumeTag {
+    /// Returns the flags telling which of the GUIDs are valid.
+    pub fn flags(&self) -> StivaleBootVolumeTagFlags {
+        self.flags
+    }
+
+    /// Returns the GUID of the volume the kernel was loaded from, or `None` if the bootloader
+    /// did not report a valid GUID.
+    pub fn guid(&self) -> Option<Guid> {
+        if self.flags.contains(StivaleBootVolumeTagFlags::GUID_VALID) {
+            Some(self.guid)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the GUID of the partition the kernel was loaded from, or `None` if the
+    /// bootloader did not report a valid partition GUID.
+    pub fn part_guid(&self) -> Option<Guid> {
+        if self
+            .flags
+            .contains(StivaleBootVolumeTagFlags::PART_GUID_VALID)
+        {
+            Some(self.part_guid)
+        } else {
+            None
+        }
+    }
+}
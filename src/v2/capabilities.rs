@@ -0,0 +1,110 @@
+//! This module contains the summary of the tags provided by the bootloader.
+
+use super::ident::*;
+use super::StivaleStruct;
+
+bitflags::bitflags! {
+    /// Bitfield recording which of the well-known struct tags are present in the tag chain.
+    pub struct Capabilities: u32 {
+        const PMRS                 = 1 << 0;
+        const KERNEL_BASE_ADDRESS  = 1 << 1;
+        const COMMAND_LINE         = 1 << 2;
+        const MEMORY_MAP           = 1 << 3;
+        const FRAMEBUFFER          = 1 << 4;
+        const EDID                 = 1 << 5;
+        const TEXT_MODE            = 1 << 6;
+        const FB_MTRR              = 1 << 7;
+        const TERMINAL             = 1 << 8;
+        const MODULES              = 1 << 9;
+        const RSDP                 = 1 << 10;
+        const SMBIOS               = 1 << 11;
+        const EPOCH                = 1 << 12;
+        const FIRMWARE             = 1 << 13;
+        const EFI                  = 1 << 14;
+        const KERNEL_FILE          = 1 << 15;
+        const KERNEL_FILE_V2       = 1 << 16;
+        const BOOT_VOLUME          = 1 << 17;
+        const KERNEL_SLIDE         = 1 << 18;
+        const SMP                  = 1 << 19;
+        const PXE                  = 1 << 20;
+        const UART                 = 1 << 21;
+        const DEVICE_TREE          = 1 << 22;
+        /// The HHDM tag, which shares its identifier with the legacy VMAP tag.
+        const HHDM                 = 1 << 23;
+    }
+}
+
+impl Capabilities {
+    /// Returns the capability of the struct tag with the provided identifier, or an empty set
+    /// if the identifier is unknown.
+    pub fn from_identifier(identifier: u64) -> Self {
+        match identifier {
+            STIVALE2_STRUCT_TAG_PMRS_ID => Self::PMRS,
+            STIVALE2_STRUCT_TAG_KERNEL_BASE_ADDRESS_ID => Self::KERNEL_BASE_ADDRESS,
+            STIVALE2_STRUCT_TAG_CMDLINE_ID => Self::COMMAND_LINE,
+            STIVALE2_STRUCT_TAG_MEMMAP_ID => Self::MEMORY_MAP,
+            STIVALE2_STRUCT_TAG_FRAMEBUFFER_ID => Self::FRAMEBUFFER,
+            STIVALE2_STRUCT_TAG_EDID_ID => Self::EDID,
+            STIVALE2_STRUCT_TAG_TEXTMODE_ID => Self::TEXT_MODE,
+            STIVALE2_STRUCT_TAG_FB_MTRR_ID => Self::FB_MTRR,
+            STIVALE2_STRUCT_TAG_TERMINAL_ID => Self::TERMINAL,
+            STIVALE2_STRUCT_TAG_MODULES_ID => Self::MODULES,
+            STIVALE2_STRUCT_TAG_RSDP_ID => Self::RSDP,
+            STIVALE2_STRUCT_TAG_SMBIOS_ID => Self::SMBIOS,
+            STIVALE2_STRUCT_TAG_EPOCH_ID => Self::EPOCH,
+            STIVALE2_STRUCT_TAG_FIRMWARE_ID => Self::FIRMWARE,
+            STIVALE2_STRUCT_TAG_EFI_SYSTEM_TABLE_ID => Self::EFI,
+            STIVALE2_STRUCT_TAG_KERNEL_FILE_ID => Self::KERNEL_FILE,
+            STIVALE2_STRUCT_TAG_KERNEL_FILE_V2_ID => Self::KERNEL_FILE_V2,
+            STIVALE2_STRUCT_TAG_BOOT_VOLUME_ID => Self::BOOT_VOLUME,
+            STIVALE2_STRUCT_TAG_KERNEL_SLIDE_ID => Self::KERNEL_SLIDE,
+            STIVALE2_STRUCT_TAG_SMP_ID => Self::SMP,
+            STIVALE2_STRUCT_TAG_PXE_SERVER_INFO_ID => Self::PXE,
+            STIVALE2_STRUCT_TAG_MMIO32_UART_ID => Self::UART,
+            STIVALE2_STRUCT_TAG_DTB_ID => Self::DEVICE_TREE,
+            STIVALE2_STRUCT_TAG_HHDM_ID => Self::HHDM,
+            _ => Self::empty(),
+        }
+    }
+}
+
+impl StivaleStruct {
+    /// Walks the tag chain once and returns which of the well-known struct tags are present.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let epoch = StivaleEpochTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_EPOCH_ID, next: 0 },
+    ///     epoch: 0,
+    /// };
+    /// let slide = StivaleKernelSlideTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_KERNEL_SLIDE_ID, next: 0 },
+    ///     kernel_slide: 0,
+    /// };
+    /// let hhdm = StivaleHhdmTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_HHDM_ID, next: 0 },
+    ///     addr: 0xffff800000000000,
+    /// };
+    ///
+    /// let mut buffer = [0u8; 512];
+    /// let mut builder = StivaleStructBuilder::new(&mut buffer).unwrap();
+    /// builder.append(&epoch).unwrap();
+    /// builder.append(&slide).unwrap();
+    /// builder.append(&hhdm).unwrap();
+    ///
+    /// let (offset, _length) = builder.finish();
+    /// let stivale_struct = unsafe { &*(buffer[offset..].as_ptr() as *const StivaleStruct) };
+    ///
+    /// assert_eq!(
+    ///     stivale_struct.capabilities(),
+    ///     Capabilities::EPOCH | Capabilities::KERNEL_SLIDE | Capabilities::HHDM
+    /// );
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        self.tags()
+            .fold(Capabilities::empty(), |capabilities, tag| {
+                capabilities | Capabilities::from_identifier(tag.identifier)
+            })
+    }
+}
@@ -0,0 +1,131 @@
+//! This module contains the helpers of the stivale2 command line tag.
+
+use super::tag::StivaleCommandLineTag;
+use super::utils;
+
+/// The maximum length of the command line that is scanned for the null terminator, which
+/// protects against a missing terminator walking off into unmapped memory.
+pub const MAX_COMMAND_LINE_LEN: usize = 4096;
+
+/// Iterator over the quoting-aware arguments of the command line, see
+/// [StivaleCommandLineTag::args_quoted].
+struct QuotedArgs<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for QuotedArgs<'a> {
+    type Item = (&'a str, Option<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.rest.as_bytes();
+        let start = bytes.iter().position(|b| !b.is_ascii_whitespace())?;
+
+        let mut end = start;
+        let mut in_quotes = false;
+
+        while end < bytes.len() {
+            match bytes[end] {
+                b'\\' => end += 1,
+                b'"' => in_quotes = !in_quotes,
+                b if b.is_ascii_whitespace() && !in_quotes => break,
+                _ => {}
+            }
+
+            end += 1;
+        }
+
+        let end = end.min(bytes.len());
+        let arg = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+
+        Some(match arg.split_once('=') {
+            Some((key, value)) => (strip_quotes(key), Some(strip_quotes(value))),
+            None => (strip_quotes(arg), None),
+        })
+    }
+}
+
+/// Strips the leading quote of the string and the matching trailing quote, if there is one and
+/// it is not escaped.
+fn strip_quotes(s: &str) -> &str {
+    let inner = match s.strip_prefix('"') {
+        Some(inner) => inner,
+        None => return s,
+    };
+
+    match inner.strip_suffix('"') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => inner,
+    }
+}
+
+impl StivaleCommandLineTag {
+    /// Returns the null-terminated command line as a rust string. Returns an empty string if
+    /// the command line pointer is null. At most [MAX_COMMAND_LINE_LEN] bytes are read, and
+    /// the string is truncated before the first invalid UTF-8 byte.
+    pub fn as_str(&self) -> &str {
+        if self.command_line == 0 {
+            return "";
+        }
+
+        unsafe { utils::c_str_from_ptr(self.command_line as *const u8, MAX_COMMAND_LINE_LEN) }
+    }
+
+    /// Returns an iterator over the arguments of the command line as `(key, value)` pairs. The
+    /// command line is split on whitespace and each argument is then split on the first `=`.
+    /// Arguments without a `=` are yielded as `(flag, None)`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let cmdline = b"root=/dev/sda1 debug\0";
+    /// let tag = StivaleCommandLineTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_CMDLINE_ID, next: 0 },
+    ///     command_line: cmdline.as_ptr() as u64,
+    /// };
+    ///
+    /// let mut args = tag.args();
+    /// assert_eq!(args.next(), Some(("root", Some("/dev/sda1"))));
+    /// assert_eq!(args.next(), Some(("debug", None)));
+    /// assert_eq!(args.next(), None);
+    /// ```
+    pub fn args(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.as_str()
+            .split_whitespace()
+            .map(|arg| match arg.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (arg, None),
+            })
+    }
+
+    /// Returns an iterator over the arguments of the command line as `(key, value)` pairs like
+    /// [StivaleCommandLineTag::args], but whitespace inside double quotes does not split an
+    /// argument and the quotes surrounding a key or a value are stripped.
+    ///
+    /// A quote preceded by a backslash does not start or end a quoted span. As the arguments
+    /// are borrowed from the command line, such escape sequences are kept verbatim. An
+    /// unterminated quote extends to the end of the command line.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let cmdline = concat!(r#"init="/sbin/init --foo" empty="" msg="say \"hi\"" debug tail="a b"#, "\0");
+    /// let tag = StivaleCommandLineTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_CMDLINE_ID, next: 0 },
+    ///     command_line: cmdline.as_ptr() as u64,
+    /// };
+    ///
+    /// let mut args = tag.args_quoted();
+    /// assert_eq!(args.next(), Some(("init", Some("/sbin/init --foo"))));
+    /// assert_eq!(args.next(), Some(("empty", Some(""))));
+    /// assert_eq!(args.next(), Some(("msg", Some(r#"say \"hi\""#))));
+    /// assert_eq!(args.next(), Some(("debug", None)));
+    /// assert_eq!(args.next(), Some(("tail", Some("a b"))));
+    /// assert_eq!(args.next(), None);
+    /// ```
+    pub fn args_quoted(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        QuotedArgs {
+            rest: self.as_str(),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! This module contains the helpers of the stivale2 device tree blob tag.
+
+use super::tag::StivaleDeviceTreeTag;
+use super::utils;
+
+/// The magic value at the start of a flattened device tree blob.
+pub const FDT_MAGIC: u32 = 0xd00dfeed;
+
+/// Token marking the beginning of a node in the structure block.
+const FDT_BEGIN_NODE: u32 = 0x1;
+/// Token marking the end of a node in the structure block.
+const FDT_END_NODE: u32 = 0x2;
+/// Token marking a property in the structure block.
+const FDT_PROP: u32 = 0x3;
+/// Token which is ignored when walking the structure block.
+const FDT_NOP: u32 = 0x4;
+
+/// Reads the big-endian `u32` at the provided offset of the blob.
+fn read_be_u32(blob: &[u8], offset: usize) -> Option<u32> {
+    let bytes = blob.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+impl StivaleDeviceTreeTag {
+    /// Returns the device tree blob as a byte slice. Returns an empty slice if the address or
+    /// the size of the blob is zero.
+    pub fn as_slice(&self) -> &[u8] {
+        if self.address == 0 || self.size == 0 {
+            return &[];
+        }
+
+        unsafe { core::slice::from_raw_parts(self.address as *const u8, self.size as usize) }
+    }
+
+    /// Returns true if the device tree blob starts with the big-endian [FDT_MAGIC].
+    pub fn magic_valid(&self) -> bool {
+        match self.as_slice() {
+            [a, b, c, d, ..] => u32::from_be_bytes([*a, *b, *c, *d]) == FDT_MAGIC,
+            _ => false,
+        }
+    }
+
+    /// Returns the `bootargs` property of the `/chosen` node of the device tree, which holds the
+    /// kernel command line on platforms such as aarch64. Returns `None` if the blob is not a
+    /// valid flattened device tree, if there is no such property or if it is not valid UTF-8.
+    ///
+    /// This only walks the structure block looking for this property and is not a general
+    /// device tree parser.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut blob = [0u8; 128];
+    /// let mut put = |offset: usize, bytes: &[u8]| {
+    ///     blob[offset..offset + bytes.len()].copy_from_slice(bytes)
+    /// };
+    ///
+    /// // The header, followed by an empty memory reservation block.
+    /// for (i, value) in [FDT_MAGIC, 128, 56, 116, 40, 17, 16, 0, 9, 60].iter().enumerate() {
+    ///     put(i * 4, &value.to_be_bytes());
+    /// }
+    /// // The structure block, containing the root node and the `/chosen` node.
+    /// put(56, b"\0\0\0\x01\0\0\0\0\0\0\0\x01chosen\0\0\0\0\0\x03\0\0\0\x0e\0\0\0\0\
+    ///     console=ttyS0\0\0\0\0\0\0\x02\0\0\0\x02\0\0\0\x09");
+    /// // The strings block.
+    /// put(116, b"bootargs\0");
+    ///
+    /// let tag = StivaleDeviceTreeTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_DTB_ID, next: 0 },
+    ///     address: blob.as_ptr() as u64,
+    ///     size: blob.len() as u64,
+    /// };
+    ///
+    /// assert_eq!(tag.bootargs(), Some("console=ttyS0"));
+    /// ```
+    pub fn bootargs(&self) -> Option<&str> {
+        if !self.magic_valid() {
+            return None;
+        }
+
+        let blob = self.as_slice();
+        let strings = blob.get(read_be_u32(blob, 12)? as usize..)?;
+
+        let mut offset = read_be_u32(blob, 8)? as usize;
+        let mut depth = 0usize;
+        let mut in_chosen = false;
+
+        loop {
+            let token = read_be_u32(blob, offset)?;
+            offset += 4;
+
+            match token {
+                FDT_BEGIN_NODE => {
+                    let rest = blob.get(offset..)?;
+                    let name_len = rest.iter().position(|&b| b == 0)?;
+
+                    depth += 1;
+                    if depth == 2 {
+                        in_chosen = &rest[..name_len] == b"chosen";
+                    }
+
+                    offset = (offset + name_len + 1).next_multiple_of(4);
+                }
+                FDT_END_NODE => {
+                    // The whole `/chosen` node was walked without finding the property.
+                    if depth == 2 && in_chosen {
+                        return None;
+                    }
+
+                    depth = depth.checked_sub(1)?;
+                }
+                FDT_PROP => {
+                    let len = read_be_u32(blob, offset)? as usize;
+                    let name_offset = read_be_u32(blob, offset + 4)? as usize;
+                    let value = blob.get(offset + 8..(offset + 8).checked_add(len)?)?;
+
+                    if in_chosen && depth == 2 {
+                        let name = strings.get(name_offset..)?;
+
+                        if utils::str_from_slice_checked(name) == Ok("bootargs") {
+                            return utils::str_from_slice_checked(value).ok();
+                        }
+                    }
+
+                    offset = (offset + 8 + len).next_multiple_of(4);
+                }
+                FDT_NOP => {}
+                // Either the end of the structure block or an invalid token.
+                _ => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! This module contains a parser for the EDID information provided by the stivale2 EDID tag.
+
+use core::convert::TryInto;
+
+use super::tag::StivaleEdidInfoTag;
+
+/// The length of the EDID base block.
+const EDID_BLOCK_LEN: usize = 128;
+/// The fixed header pattern at the start of the EDID base block.
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+/// The offset of the first detailed timing descriptor in the EDID base block.
+const DETAILED_TIMING_OFFSET: usize = 54;
+/// The length of a single detailed timing descriptor.
+const DETAILED_TIMING_LEN: usize = 18;
+/// The amount of detailed timing descriptors in the EDID base block.
+const DETAILED_TIMING_COUNT: usize = 4;
+
+/// A detailed timing descriptor of the EDID base block, describing a video mode supported by
+/// the monitor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetailedTiming {
+    /// The pixel clock in kHz.
+    pub pixel_clock_khz: u32,
+    /// The horizontal active pixels.
+    pub h_active: u16,
+    /// The horizontal blanking pixels.
+    pub h_blanking: u16,
+    /// The vertical active lines.
+    pub v_active: u16,
+    /// The vertical blanking lines.
+    pub v_blanking: u16,
+    /// The horizontal front porch in pixels.
+    pub h_sync_offset: u16,
+    /// The horizontal sync pulse width in pixels.
+    pub h_sync_width: u16,
+    /// The vertical front porch in lines.
+    pub v_sync_offset: u16,
+    /// The vertical sync pulse width in lines.
+    pub v_sync_width: u16,
+}
+
+impl DetailedTiming {
+    /// Parses a detailed timing descriptor, returning `None` if it is a display descriptor.
+    fn parse(descriptor: &[u8]) -> Option<Self> {
+        let pixel_clock = u16::from_le_bytes([descriptor[0], descriptor[1]]);
+
+        if pixel_clock == 0 {
+            return None;
+        }
+
+        let d = |index: usize| descriptor[index] as u16;
+
+        Some(Self {
+            pixel_clock_khz: pixel_clock as u32 * 10,
+            h_active: d(2) | ((d(4) & 0xf0) << 4),
+            h_blanking: d(3) | ((d(4) & 0x0f) << 8),
+            v_active: d(5) | ((d(7) & 0xf0) << 4),
+            v_blanking: d(6) | ((d(7) & 0x0f) << 8),
+            h_sync_offset: d(8) | ((d(11) & 0xc0) << 2),
+            h_sync_width: d(9) | ((d(11) & 0x30) << 4),
+            v_sync_offset: (d(10) >> 4) | ((d(11) & 0x0c) << 2),
+            v_sync_width: (d(10) & 0x0f) | ((d(11) & 0x03) << 4),
+        })
+    }
+}
+
+/// Iterator over the detailed timing descriptors of the EDID base block. Display descriptors are
+/// skipped.
+#[derive(Clone)]
+pub struct DetailedTimingIter<'a> {
+    block: &'a [u8; EDID_BLOCK_LEN],
+    /// The index of the descriptor that we are about to parse.
+    current: usize,
+}
+
+impl<'a> Iterator for DetailedTimingIter<'a> {
+    type Item = DetailedTiming;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current < DETAILED_TIMING_COUNT {
+            let offset = DETAILED_TIMING_OFFSET + self.current * DETAILED_TIMING_LEN;
+            self.current += 1;
+
+            let timing = DetailedTiming::parse(&self.block[offset..offset + DETAILED_TIMING_LEN]);
+
+            if timing.is_some() {
+                return timing;
+            }
+        }
+
+        None
+    }
+}
+
+/// Parsed EDID base block, as acquired by the firmware.
+///
+/// ## Example
+/// ```rust
+/// # use stivale_boot::v2::EdidInfo;
+/// let mut block = [0u8; 128];
+/// block[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+/// block[8..10].copy_from_slice(&[0x10, 0xac]);
+/// block[54..56].copy_from_slice(&14850u16.to_le_bytes());
+/// (block[56], block[58]) = (0x80, 0x70);
+/// (block[59], block[61]) = (0x38, 0x40);
+/// block[127] = block.iter().fold(0u8, |sum, byte| sum.wrapping_sub(*byte));
+///
+/// let edid = EdidInfo::parse(&block).unwrap();
+/// assert_eq!(&edid.manufacturer_id(), b"DEL");
+/// assert_eq!(edid.preferred_resolution(), (1920, 1080));
+///
+/// let mut timings = edid.detailed_timings();
+/// assert_eq!(timings.next().map(|timing| timing.pixel_clock_khz), Some(148500));
+/// assert_eq!(timings.next(), None);
+/// ```
+#[derive(Clone, Copy)]
+pub struct EdidInfo<'a> {
+    block: &'a [u8; EDID_BLOCK_LEN],
+}
+
+impl<'a> EdidInfo<'a> {
+    /// Parses the EDID base block from the provided bytes. Returns `None` if the bytes are shorter
+    /// than 128 bytes, the header magic is invalid or the checksum fails.
+    pub fn parse(bytes: &'a [u8]) -> Option<Self> {
+        let block: &[u8; EDID_BLOCK_LEN] = bytes.get(..EDID_BLOCK_LEN)?.try_into().ok()?;
+
+        if block[..EDID_HEADER.len()] != EDID_HEADER {
+            return None;
+        }
+
+        if block.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return None;
+        }
+
+        Some(Self { block })
+    }
+
+    /// Returns the three letter manufacturer ID as ASCII bytes.
+    pub fn manufacturer_id(&self) -> [u8; 3] {
+        let id = u16::from_be_bytes([self.block[8], self.block[9]]);
+        let letter = |shift: u16| b'A' - 1 + ((id >> shift) & 0x1f) as u8;
+
+        [letter(10), letter(5), letter(0)]
+    }
+
+    /// Returns the manufacturer product code.
+    pub fn product_code(&self) -> u16 {
+        u16::from_le_bytes([self.block[10], self.block[11]])
+    }
+
+    /// Returns the serial number.
+    pub fn serial(&self) -> u32 {
+        u32::from_le_bytes([
+            self.block[12],
+            self.block[13],
+            self.block[14],
+            self.block[15],
+        ])
+    }
+
+    /// Returns the preferred `(width, height)` resolution in pixels, taken from the first
+    /// detailed timing descriptor.
+    pub fn preferred_resolution(&self) -> (u16, u16) {
+        let descriptor =
+            &self.block[DETAILED_TIMING_OFFSET..DETAILED_TIMING_OFFSET + DETAILED_TIMING_LEN];
+
+        let width = descriptor[2] as u16 | ((descriptor[4] as u16 & 0xf0) << 4);
+        let height = descriptor[5] as u16 | ((descriptor[7] as u16 & 0xf0) << 4);
+
+        (width, height)
+    }
+
+    /// Returns an iterator over the detailed timing descriptors, skipping display descriptors.
+    pub fn detailed_timings(&self) -> DetailedTimingIter<'a> {
+        DetailedTimingIter {
+            block: self.block,
+            current: 0,
+        }
+    }
+}
+
+impl StivaleEdidInfoTag {
+    /// Parses the EDID information. Returns `None` if the information is shorter than 128 bytes
+    /// or is not a valid EDID base block.
+    pub fn parse(&self) -> Option<EdidInfo<'_>> {
+        EdidInfo::parse(self.as_slice())
+    }
+}
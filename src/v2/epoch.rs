@@ -0,0 +1,66 @@
+//! This module contains the helpers of the stivale2 epoch tag.
+
+use super::tag::StivaleEpochTag;
+
+/// Broken-down UTC date and time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i64,
+    /// Month of the year, in the range `1..=12`.
+    pub month: u8,
+    /// Day of the month, in the range `1..=31`.
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// Converts the provided UNIX timestamp into a broken-down date and time using the
+    /// civil-from-days algorithm.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::DateTime;
+    /// let datetime = DateTime::from_unix(1700000000);
+    ///
+    /// assert_eq!((datetime.year, datetime.month, datetime.day), (2023, 11, 14));
+    /// assert_eq!((datetime.hour, datetime.minute, datetime.second), (22, 13, 20));
+    /// ```
+    pub fn from_unix(timestamp: u64) -> Self {
+        let days = (timestamp / 86400) as i64;
+        let seconds = timestamp % 86400;
+
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+        }
+    }
+}
+
+impl StivaleEpochTag {
+    /// Returns the UNIX epoch at boot, which is read from system RTC.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Returns the UNIX epoch at boot as a broken-down date and time.
+    pub fn to_datetime(&self) -> DateTime {
+        DateTime::from_unix(self.epoch())
+    }
+}
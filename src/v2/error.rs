@@ -0,0 +1,44 @@
+/// Error type returned by the fallible helpers of the stivale2 tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StivaleError {
+    /// The framebuffer uses a memory model other than RGB, which is not supported by the
+    /// drawing helpers.
+    UnsupportedMemoryModel(u8),
+    /// The terminal does not support context control.
+    ContextControlUnavailable,
+    /// The provided buffer is too small for the requested operation.
+    BufferTooSmall,
+    /// The length of the provided buffer does not match the length required by the operation.
+    BufferLengthMismatch,
+    /// The bootloader brand or version string is not null terminated or not valid UTF-8.
+    InvalidString,
+    /// The tag chain has more than [MAX_TAG_COUNT](super::MAX_TAG_COUNT) tags, which likely
+    /// means that it is corrupt or cyclic.
+    ChainTooLong,
+    /// A tag in the tag chain is located below the stivale2 struct, which likely means that the
+    /// `next` pointer of the previous tag is corrupt. Contains the address of the tag.
+    InvalidTagAddress(u64),
+    /// The tag with the contained identifier is not known, so its size cannot be determined.
+    UnknownTag(u64),
+    /// A pointer provided by the bootloader is null.
+    NullPointer,
+    /// A length provided by the bootloader is implausibly large.
+    InvalidLength,
+    /// The provided coordinates are outside of the bounds of the framebuffer.
+    OutOfBounds,
+}
+
+/// Error type returned by [StivaleMemoryMapTag::verify](super::StivaleMemoryMapTag::verify),
+/// containing the index of the first memory map entry violating the guarantees of the stivale2
+/// specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryMapError {
+    /// The entry starts below the previous entry, so the entries are not sorted by base address.
+    Unsorted(usize),
+    /// The entry is usable or bootloader reclaimable, but its base or its length is not 4096
+    /// byte aligned.
+    Misaligned(usize),
+    /// The entry overlaps a previous entry, while at least one of them is usable or bootloader
+    /// reclaimable.
+    Overlapping(usize),
+}
@@ -0,0 +1,33 @@
+//! This module contains the bitmap font used by the text drawing helpers of the framebuffer.
+
+/// Bitmap font of 256 glyphs of 8x16 pixels, indexed by character code. Each glyph is made of
+/// 16 rows, top to bottom, where the most significant bit of a row is its leftmost pixel.
+pub type Font8x16 = [[u8; 16]; 256];
+
+/// Built-in font covering the printable ASCII characters, derived from the public domain
+/// `font8x8` glyphs by doubling every row. All the other glyphs are blank.
+#[cfg(feature = "builtin-font")]
+pub static DEFAULT_FONT: Font8x16 = default_font();
+
+#[cfg(feature = "builtin-font")]
+const fn default_font() -> Font8x16 {
+    let mut font = [[0; 16]; 256];
+    let mut glyph = 0;
+
+    while glyph < font8x8::legacy::BASIC_LEGACY.len() {
+        let mut row = 0;
+
+        while row < 8 {
+            // The `font8x8` glyphs have their leftmost pixel in the least significant bit.
+            let bits = font8x8::legacy::BASIC_LEGACY[glyph][row].reverse_bits();
+
+            font[glyph][row * 2] = bits;
+            font[glyph][row * 2 + 1] = bits;
+            row += 1;
+        }
+
+        glyph += 1;
+    }
+
+    font
+}
@@ -0,0 +1,1013 @@
+//! This module contains the drawing helpers of the stivale2 framebuffer tag.
+
+use core::convert::TryFrom;
+
+use super::error::StivaleError;
+use super::font::Font8x16;
+use super::tag::{StivaleFramebufferMemoryModel, StivaleFramebufferTag, StivaleMemoryMapEntry};
+use super::StivaleStruct;
+
+/// The size of the file header and the info header of a BMP file.
+const BMP_HEADER_SIZE: usize = 14 + 40;
+
+/// Fills the span with copies of the byte pattern of a pixel.
+fn fill_pattern(span: &mut [u8], pattern: &[u8]) {
+    for pixel in span.chunks_exact_mut(pattern.len()) {
+        pixel.copy_from_slice(pattern);
+    }
+}
+
+/// Scales an 8-bit color channel to a channel of `size` bits.
+fn scale_channel(value: u8, size: u8) -> u32 {
+    let max = channel_max(size);
+    ((value as u64 * max as u64) / 0xff) as u32
+}
+
+/// Scales a channel of `size` bits back to an 8-bit color channel.
+fn unscale_channel(value: u32, size: u8) -> u8 {
+    let max = channel_max(size);
+
+    if max == 0 {
+        0
+    } else {
+        ((value as u64 * 0xff) / max as u64) as u8
+    }
+}
+
+/// Returns the maximum value of a channel of `size` bits.
+fn channel_max(size: u8) -> u32 {
+    if size >= 32 {
+        u32::MAX
+    } else {
+        (1 << size) - 1
+    }
+}
+
+/// Named pixel format of the framebuffer. The names describe the order of the channels from the
+/// most significant to the least significant bits of a pixel, so [PixelFormat::Bgr888] is
+/// stored in memory as blue, green and then red on little endian platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 8 bits per channel with red in the high bits and blue in the low bits, using 24 or 32
+    /// bits per pixel.
+    Bgr888,
+    /// 8 bits per channel with blue in the high bits and red in the low bits, using 24 or 32
+    /// bits per pixel.
+    Rgb888,
+    /// 5 bits of red in the high bits, 6 bits of green and 5 bits of blue in the low bits, using
+    /// 16 bits per pixel.
+    Rgb565,
+    /// Any other pixel format, described by its bpp and the `(size, shift)` of its RGB masks.
+    Other {
+        bpp: u16,
+        red: (u8, u8),
+        green: (u8, u8),
+        blue: (u8, u8),
+    },
+}
+
+/// The video mode set up by the bootloader, see [StivaleStruct::video_mode].
+#[derive(Clone, Copy)]
+pub enum VideoMode<'a> {
+    /// The bootloader set up a linear framebuffer described by the contained tag.
+    Framebuffer(&'a StivaleFramebufferTag),
+    /// The bootloader did not provide a framebuffer, so the display is left in text mode.
+    Text,
+}
+
+impl StivaleStruct {
+    /// Returns the video mode set up by the bootloader. When the kernel requests any video mode
+    /// through the any video header tag, the bootloader may either set up a framebuffer or
+    /// leave the display in text mode.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u8; 512];
+    /// let builder = StivaleStructBuilder::new(&mut buffer).unwrap();
+    /// let (offset, _length) = builder.finish();
+    /// let stivale_struct = unsafe { &*(buffer[offset..].as_ptr() as *const StivaleStruct) };
+    ///
+    /// match stivale_struct.video_mode() {
+    ///     VideoMode::Framebuffer(_) => unreachable!(),
+    ///     VideoMode::Text => {}
+    /// }
+    /// ```
+    pub fn video_mode(&self) -> VideoMode<'static> {
+        match self.framebuffer() {
+            Some(framebuffer) => VideoMode::Framebuffer(framebuffer),
+            None => VideoMode::Text,
+        }
+    }
+
+    /// Returns the memory map entry containing the framebuffer, which is expected to be of type
+    /// [StivaleMemoryMapEntryType::Framebuffer](super::StivaleMemoryMapEntryType::Framebuffer).
+    /// Returns `None` if the framebuffer or the memory map tag is missing or if the framebuffer
+    /// lies in a gap of the memory map.
+    ///
+    /// If the framebuffer address is in the higher half direct map, it is translated back to a
+    /// physical address before looking it up.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 2],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 2,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0xfd00_0000, 0x30_0000, StivaleMemoryMapEntryType::Framebuffer),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 2) };
+    /// # let mut framebuffer: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # framebuffer.header.identifier = STIVALE2_STRUCT_TAG_FRAMEBUFFER_ID;
+    /// # framebuffer.framebuffer_addr = 0xfd00_0000;
+    ///
+    /// let mut buffer = [0u8; 512];
+    /// let mut builder = StivaleStructBuilder::new(&mut buffer).unwrap();
+    /// builder.append(&framebuffer).unwrap();
+    /// builder.append(memory_map).unwrap();
+    /// let (offset, _length) = builder.finish();
+    /// let stivale_struct = unsafe { &*(buffer[offset..].as_ptr() as *const StivaleStruct) };
+    ///
+    /// let entry = stivale_struct.framebuffer_memory().unwrap();
+    /// assert_eq!(entry.entry_type(), StivaleMemoryMapEntryType::Framebuffer);
+    /// ```
+    pub fn framebuffer_memory(&self) -> Option<&'static StivaleMemoryMapEntry> {
+        let addr = self.framebuffer()?.framebuffer_addr;
+        let phys = match self.direct_map_offset() {
+            Some(offset) if addr >= offset => addr - offset,
+            _ => addr,
+        };
+
+        self.memory_map()?.region_for(phys)
+    }
+}
+
+/// Copy of the description of the framebuffer reported by the framebuffer tag, which can be
+/// passed around by value instead of borrowing the tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramebufferInfo {
+    /// The address of the framebuffer.
+    pub addr: u64,
+    /// The total width of the framebuffer in pixels.
+    pub width: u16,
+    /// The total height of the framebuffer in pixels.
+    pub height: u16,
+    /// The pitch of the framebuffer in bytes.
+    pub pitch: u16,
+    /// The amount of bits per pixel.
+    pub bpp: u16,
+    /// Memory model of the framebuffer.
+    pub memory_model: StivaleFramebufferMemoryModel,
+    /// Size of the red mask in RGB.
+    pub red_mask_size: u8,
+    /// Shift of the red mask in RGB.
+    pub red_mask_shift: u8,
+    /// Size of the green mask in RGB.
+    pub green_mask_size: u8,
+    /// Shift of the green mask in RGB.
+    pub green_mask_shift: u8,
+    /// Size of the blue mask in RGB.
+    pub blue_mask_size: u8,
+    /// Shift of the blue mask in RGB.
+    pub blue_mask_shift: u8,
+}
+
+impl StivaleFramebufferTag {
+    /// Returns a copy of the description of the framebuffer.
+    pub fn info(&self) -> FramebufferInfo {
+        FramebufferInfo {
+            addr: self.framebuffer_addr,
+            width: self.framebuffer_width,
+            height: self.framebuffer_height,
+            pitch: self.framebuffer_pitch,
+            bpp: self.framebuffer_bpp,
+            memory_model: self.memory_model(),
+            red_mask_size: self.red_mask_size,
+            red_mask_shift: self.red_mask_shift,
+            green_mask_size: self.green_mask_size,
+            green_mask_shift: self.green_mask_shift,
+            blue_mask_size: self.blue_mask_size,
+            blue_mask_shift: self.blue_mask_shift,
+        }
+    }
+
+    /// Returns the named pixel format matching the bpp and the RGB masks of the framebuffer.
+    /// Framebuffers whose memory model is not RGB are always [PixelFormat::Other].
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// tag.framebuffer_bpp = 32;
+    /// tag.memory_model = 1;
+    /// (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// assert_eq!(tag.pixel_format(), PixelFormat::Bgr888);
+    /// ```
+    pub fn pixel_format(&self) -> PixelFormat {
+        let red = (self.red_mask_size, self.red_mask_shift);
+        let green = (self.green_mask_size, self.green_mask_shift);
+        let blue = (self.blue_mask_size, self.blue_mask_shift);
+
+        match (self.memory_model(), self.framebuffer_bpp, red, green, blue) {
+            (StivaleFramebufferMemoryModel::Rgb, 24 | 32, (8, 16), (8, 8), (8, 0)) => {
+                PixelFormat::Bgr888
+            }
+            (StivaleFramebufferMemoryModel::Rgb, 24 | 32, (8, 0), (8, 8), (8, 16)) => {
+                PixelFormat::Rgb888
+            }
+            (StivaleFramebufferMemoryModel::Rgb, 16, (5, 11), (6, 5), (5, 0)) => {
+                PixelFormat::Rgb565
+            }
+            (_, bpp, red, green, blue) => PixelFormat::Other {
+                bpp,
+                red,
+                green,
+                blue,
+            },
+        }
+    }
+
+    /// Returns the amount of bytes used by a single pixel.
+    #[inline]
+    fn bytes_per_pixel(&self) -> usize {
+        self.framebuffer_bpp as usize / 8
+    }
+
+    /// Returns the byte offset of the pixel at the provided coordinates, or `None` if the
+    /// coordinates are out of bounds.
+    fn pixel_offset(&self, x: u16, y: u16) -> Option<usize> {
+        if x >= self.framebuffer_width || y >= self.framebuffer_height {
+            return None;
+        }
+
+        Some(y as usize * self.framebuffer_pitch as usize + x as usize * self.bytes_per_pixel())
+    }
+
+    /// Encodes the provided RGB color into the native pixel value of the framebuffer. Each
+    /// channel is downscaled to its mask size and shifted into place using the mask shifts.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// let pixel = tag.encode_color(0xaa, 0xbb, 0xcc);
+    /// assert_eq!(pixel, 0xaabbcc);
+    /// assert_eq!(tag.decode_color(pixel), (0xaa, 0xbb, 0xcc));
+    /// ```
+    pub fn encode_color(&self, r: u8, g: u8, b: u8) -> u32 {
+        let channel = |value: u8, size: u8, shift: u8| {
+            scale_channel(value, size)
+                .checked_shl(shift as u32)
+                .unwrap_or(0)
+        };
+
+        channel(r, self.red_mask_size, self.red_mask_shift)
+            | channel(g, self.green_mask_size, self.green_mask_shift)
+            | channel(b, self.blue_mask_size, self.blue_mask_shift)
+    }
+
+    /// Decodes the provided native pixel value of the framebuffer into an RGB color. This is the
+    /// inverse of [StivaleFramebufferTag::encode_color].
+    pub fn decode_color(&self, pixel: u32) -> (u8, u8, u8) {
+        let channel = |size: u8, shift: u8| {
+            let value = pixel.checked_shr(shift as u32).unwrap_or(0);
+            unscale_channel(value & channel_max(size), size)
+        };
+
+        (
+            channel(self.red_mask_size, self.red_mask_shift),
+            channel(self.green_mask_size, self.green_mask_shift),
+            channel(self.blue_mask_size, self.blue_mask_shift),
+        )
+    }
+
+    /// Writes the native pixel value at the provided byte offset, using the width of the
+    /// framebuffer's bpp.
+    fn write_pixel_value(&self, offset: usize, pixel: u32) {
+        let ptr = (self.framebuffer_addr as usize + offset) as *mut u8;
+
+        unsafe {
+            match self.framebuffer_bpp {
+                16 => (ptr as *mut u16).write_unaligned(pixel as u16),
+                24 => {
+                    ptr.write(pixel as u8);
+                    ptr.add(1).write((pixel >> 8) as u8);
+                    ptr.add(2).write((pixel >> 16) as u8);
+                }
+                32 => (ptr as *mut u32).write_unaligned(pixel),
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the native pixel value at the provided byte offset, using the width of the
+    /// framebuffer's bpp.
+    fn read_pixel_value(&self, offset: usize) -> u32 {
+        let ptr = (self.framebuffer_addr as usize + offset) as *const u8;
+
+        unsafe {
+            match self.framebuffer_bpp {
+                16 => (ptr as *const u16).read_unaligned() as u32,
+                24 => {
+                    ptr.read() as u32
+                        | (ptr.add(1).read() as u32) << 8
+                        | (ptr.add(2).read() as u32) << 16
+                }
+                32 => (ptr as *const u32).read_unaligned(),
+                _ => 0,
+            }
+        }
+    }
+
+    /// Returns an error if the memory model of the framebuffer is not supported by the
+    /// drawing helpers.
+    fn check_memory_model(&self) -> Result<(), StivaleError> {
+        match self.memory_model() {
+            StivaleFramebufferMemoryModel::Rgb => Ok(()),
+            StivaleFramebufferMemoryModel::Unknown(model) => {
+                Err(StivaleError::UnsupportedMemoryModel(model))
+            }
+        }
+    }
+
+    /// Writes `count` copies of the native pixel value starting at the provided byte offset. The
+    /// pixel is encoded into its byte pattern once and the whole span is then written in one pass.
+    fn fill_span(&self, offset: usize, count: usize, pixel: u32) {
+        let ptr = (self.framebuffer_addr as usize + offset) as *mut u8;
+        let span = unsafe { core::slice::from_raw_parts_mut(ptr, count * self.bytes_per_pixel()) };
+
+        match self.framebuffer_bpp {
+            32 if ptr.cast::<u32>().is_aligned() => {
+                unsafe { core::slice::from_raw_parts_mut(ptr as *mut u32, count) }.fill(pixel)
+            }
+            32 => fill_pattern(span, &pixel.to_ne_bytes()),
+            24 => fill_pattern(span, &pixel.to_le_bytes()[..3]),
+            16 => fill_pattern(span, &(pixel as u16).to_ne_bytes()),
+            _ => {}
+        }
+    }
+
+    /// Plots a pixel with the provided RGB color at the provided coordinates. The color is packed
+    /// using the RGB masks of the framebuffer and framebuffers with a bpp of 16, 24 and 32 are
+    /// supported. Out of bounds coordinates are ignored.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u8; 4 * 4 * 4];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 4;
+    /// # tag.framebuffer_height = 4;
+    /// # tag.framebuffer_pitch = 16;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// tag.put_pixel(1, 2, (0x12, 0x34, 0x56)).unwrap();
+    /// assert_eq!(tag.get_pixel(1, 2), Some((0x12, 0x34, 0x56)));
+    /// assert_eq!(tag.get_pixel(4, 0), None);
+    /// ```
+    pub fn put_pixel(&self, x: u16, y: u16, rgb: (u8, u8, u8)) -> Result<(), StivaleError> {
+        self.check_memory_model()?;
+
+        if let Some(offset) = self.pixel_offset(x, y) {
+            self.write_pixel_value(offset, self.encode_color(rgb.0, rgb.1, rgb.2));
+        }
+
+        Ok(())
+    }
+
+    /// Reads back the RGB color of the pixel at the provided coordinates, or `None` if the
+    /// coordinates are out of bounds or the memory model of the framebuffer is not RGB.
+    pub fn get_pixel(&self, x: u16, y: u16) -> Option<(u8, u8, u8)> {
+        self.check_memory_model().ok()?;
+        self.pixel_offset(x, y)
+            .map(|offset| self.decode_color(self.read_pixel_value(offset)))
+    }
+
+    /// Fills the rectangle with the provided top-left corner and dimensions with the provided RGB
+    /// color. The rectangle is clipped against the bounds of the framebuffer.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u32; 4 * 3];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 4;
+    /// # tag.framebuffer_height = 3;
+    /// # tag.framebuffer_pitch = 16;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// tag.fill_rect(1, 1, 10, 10, (0x12, 0x34, 0x56)).unwrap();
+    /// assert_eq!(
+    ///     buffer,
+    ///     [
+    ///         0, 0, 0, 0,
+    ///         0, 0x123456, 0x123456, 0x123456,
+    ///         0, 0x123456, 0x123456, 0x123456,
+    ///     ]
+    /// );
+    /// ```
+    pub fn fill_rect(
+        &self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        rgb: (u8, u8, u8),
+    ) -> Result<(), StivaleError> {
+        self.check_memory_model()?;
+
+        if x >= self.framebuffer_width || y >= self.framebuffer_height {
+            return Ok(());
+        }
+
+        let width = width.min(self.framebuffer_width - x) as usize;
+        let height = height.min(self.framebuffer_height - y) as usize;
+        let pixel = self.encode_color(rgb.0, rgb.1, rgb.2);
+        let start =
+            y as usize * self.framebuffer_pitch as usize + x as usize * self.bytes_per_pixel();
+
+        for row in 0..height {
+            self.fill_span(start + row * self.framebuffer_pitch as usize, width, pixel);
+        }
+
+        Ok(())
+    }
+
+    /// Draws a horizontal line of `len` pixels starting at the provided coordinates with the
+    /// provided RGB color. The line is clipped against the bounds of the framebuffer.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    pub fn draw_hline(
+        &self,
+        x: u16,
+        y: u16,
+        len: u16,
+        rgb: (u8, u8, u8),
+    ) -> Result<(), StivaleError> {
+        self.check_memory_model()?;
+
+        if let Some(offset) = self.pixel_offset(x, y) {
+            let len = len.min(self.framebuffer_width - x) as usize;
+            self.fill_span(offset, len, self.encode_color(rgb.0, rgb.1, rgb.2));
+        }
+
+        Ok(())
+    }
+
+    /// Draws a vertical line of `len` pixels starting at the provided coordinates with the
+    /// provided RGB color. The line is clipped against the bounds of the framebuffer.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u32; 4 * 4];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 4;
+    /// # tag.framebuffer_height = 4;
+    /// # tag.framebuffer_pitch = 16;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// tag.draw_hline(2, 0, 10, (0xff, 0xff, 0xff)).unwrap();
+    /// tag.draw_vline(0, 1, 10, (0xff, 0, 0)).unwrap();
+    /// assert_eq!(
+    ///     buffer,
+    ///     [
+    ///         0, 0, 0xffffff, 0xffffff,
+    ///         0xff0000, 0, 0, 0,
+    ///         0xff0000, 0, 0, 0,
+    ///         0xff0000, 0, 0, 0,
+    ///     ]
+    /// );
+    /// ```
+    pub fn draw_vline(
+        &self,
+        x: u16,
+        y: u16,
+        len: u16,
+        rgb: (u8, u8, u8),
+    ) -> Result<(), StivaleError> {
+        self.check_memory_model()?;
+
+        if let Some(offset) = self.pixel_offset(x, y) {
+            let len = len.min(self.framebuffer_height - y) as usize;
+            let pixel = self.encode_color(rgb.0, rgb.1, rgb.2);
+
+            for row in 0..len {
+                self.write_pixel_value(offset + row * self.framebuffer_pitch as usize, pixel);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Clears the whole framebuffer with the provided RGB color.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// // A 2x2 framebuffer with 24 bits per pixel and a padded pitch of 8 bytes.
+    /// let mut buffer = [0u8; 8 * 2];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 2;
+    /// # tag.framebuffer_height = 2;
+    /// # tag.framebuffer_pitch = 8;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    /// # tag.framebuffer_bpp = 24;
+    ///
+    /// tag.clear((0x12, 0x34, 0x56)).unwrap();
+    /// assert_eq!(
+    ///     buffer,
+    ///     [0x56, 0x34, 0x12, 0x56, 0x34, 0x12, 0, 0, 0x56, 0x34, 0x12, 0x56, 0x34, 0x12, 0, 0]
+    /// );
+    /// ```
+    pub fn clear(&self, rgb: (u8, u8, u8)) -> Result<(), StivaleError> {
+        self.fill_rect(0, 0, self.framebuffer_width, self.framebuffer_height, rgb)
+    }
+
+    /// Scrolls the contents of the framebuffer up by `rows` rows and fills the newly exposed
+    /// rows at the bottom with the provided RGB color. Scrolling by at least the height of the
+    /// framebuffer clears it.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [1u32, 2, 3, 4, 5, 6];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 2;
+    /// # tag.framebuffer_height = 3;
+    /// # tag.framebuffer_pitch = 8;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// tag.scroll_up(1, (0, 0, 0)).unwrap();
+    /// assert_eq!(buffer, [3, 4, 5, 6, 0, 0]);
+    /// ```
+    pub fn scroll_up(&self, rows: u16, fill: (u8, u8, u8)) -> Result<(), StivaleError> {
+        self.check_memory_model()?;
+
+        let height = self.framebuffer_height;
+        let rows = rows.min(height);
+        let pitch = self.framebuffer_pitch as usize;
+        let row_len = self.framebuffer_width as usize * self.bytes_per_pixel();
+        let addr = self.framebuffer_addr as usize;
+
+        // The rows are copied top-down, so a source row is always read before it is overwritten.
+        for row in 0..(height - rows) as usize {
+            let src = (addr + (row + rows as usize) * pitch) as *const u8;
+            let dst = (addr + row * pitch) as *mut u8;
+
+            unsafe { core::ptr::copy(src, dst, row_len) };
+        }
+
+        self.fill_rect(0, height - rows, self.framebuffer_width, rows, fill)
+    }
+
+    /// Draws the glyph of the provided character from the provided font with its top-left corner
+    /// at the provided coordinates. Set pixels of the glyph are drawn with the `fg` color and
+    /// the others with the `bg` color. The glyph is clipped against the bounds of the framebuffer.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    pub fn draw_char(
+        &self,
+        x: u16,
+        y: u16,
+        c: u8,
+        fg: (u8, u8, u8),
+        bg: (u8, u8, u8),
+        font: &Font8x16,
+    ) -> Result<(), StivaleError> {
+        self.check_memory_model()?;
+
+        let fg = self.encode_color(fg.0, fg.1, fg.2);
+        let bg = self.encode_color(bg.0, bg.1, bg.2);
+
+        for (dy, bits) in font[c as usize].iter().enumerate() {
+            for dx in 0..8 {
+                let pixel = if bits & (0x80 >> dx) != 0 { fg } else { bg };
+                let offset = self.pixel_offset(x.saturating_add(dx), y.saturating_add(dy as u16));
+
+                if let Some(offset) = offset {
+                    self.write_pixel_value(offset, pixel);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws the provided string with the provided font, starting with its top-left corner at
+    /// the provided coordinates. Every character advances by 8 pixels and a newline moves back to
+    /// the starting column, 16 pixels lower. Characters outside of the font are drawn as `?`.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u32; 16 * 16];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 16;
+    /// # tag.framebuffer_height = 16;
+    /// # tag.framebuffer_pitch = 64;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// let mut font: Font8x16 = [[0; 16]; 256];
+    /// font[b'|' as usize] = [0x80; 16];
+    ///
+    /// tag.draw_str(0, 0, "||", (0xff, 0xff, 0xff), (0, 0, 0x11), &font).unwrap();
+    /// assert_eq!(buffer[..10], [0xffffff, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0xffffff, 0x11]);
+    /// ```
+    pub fn draw_str(
+        &self,
+        x: u16,
+        y: u16,
+        s: &str,
+        fg: (u8, u8, u8),
+        bg: (u8, u8, u8),
+        font: &Font8x16,
+    ) -> Result<(), StivaleError> {
+        let (mut cx, mut cy) = (x, y);
+
+        for c in s.chars() {
+            if c == '\n' {
+                cx = x;
+                cy = cy.saturating_add(16);
+                continue;
+            }
+
+            let c = u8::try_from(c).unwrap_or(b'?');
+            self.draw_char(cx, cy, c, fg, bg, font)?;
+            cx = cx.saturating_add(8);
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the current contents of the framebuffer as a 24-bit BMP file into the provided
+    /// buffer, returning the amount of bytes written.
+    ///
+    /// Returns [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer
+    /// is not RGB, [StivaleError::BufferTooSmall] if the BMP file does not fit in the buffer and
+    /// [StivaleError::InvalidLength] if its size does not fit in the 32-bit size of the BMP
+    /// header. A framebuffer with a zero width or height produces only the headers.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0x00ff0000u32, 0x000000ff];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 1;
+    /// # tag.framebuffer_height = 2;
+    /// # tag.framebuffer_pitch = 4;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// let mut bmp = [0u8; 62];
+    /// assert_eq!(tag.write_bmp(&mut bmp), Ok(62));
+    /// assert_eq!(&bmp[..2], b"BM");
+    /// // The rows are stored bottom-up as BGR, padded to 4 bytes.
+    /// assert_eq!(bmp[54..], [0xff, 0, 0, 0, 0, 0, 0xff, 0]);
+    ///
+    /// assert_eq!(tag.write_bmp(&mut [0; 61]), Err(StivaleError::BufferTooSmall));
+    ///
+    /// tag.framebuffer_width = 0;
+    /// assert_eq!(tag.write_bmp(&mut bmp), Ok(54));
+    /// ```
+    pub fn write_bmp(&self, out: &mut [u8]) -> Result<usize, StivaleError> {
+        self.check_memory_model()?;
+
+        let width = self.framebuffer_width as usize;
+        let height = self.framebuffer_height as usize;
+        let row_size = width
+            .checked_mul(3)
+            .and_then(|size| size.checked_next_multiple_of(4))
+            .ok_or(StivaleError::InvalidLength)?;
+        let image_size = row_size
+            .checked_mul(height)
+            .ok_or(StivaleError::InvalidLength)?;
+        let file_size = image_size
+            .checked_add(BMP_HEADER_SIZE)
+            .filter(|&size| size <= u32::MAX as usize)
+            .ok_or(StivaleError::InvalidLength)?;
+
+        if out.len() < file_size {
+            return Err(StivaleError::BufferTooSmall);
+        }
+
+        let (header, image) = out[..file_size].split_at_mut(BMP_HEADER_SIZE);
+        let fields: [&[u8]; 13] = [
+            // File header.
+            b"BM",
+            &(file_size as u32).to_le_bytes(),
+            &0u32.to_le_bytes(),
+            &(BMP_HEADER_SIZE as u32).to_le_bytes(),
+            // Info header.
+            &40u32.to_le_bytes(),
+            &(width as i32).to_le_bytes(),
+            &(height as i32).to_le_bytes(),
+            &1u16.to_le_bytes(),
+            &24u16.to_le_bytes(),
+            &0u32.to_le_bytes(),
+            &(image_size as u32).to_le_bytes(),
+            &[0; 8],
+            &[0; 8],
+        ];
+
+        let mut offset = 0;
+        for field in fields {
+            header[offset..offset + field.len()].copy_from_slice(field);
+            offset += field.len();
+        }
+
+        // An empty framebuffer only produces the headers.
+        if image_size == 0 {
+            return Ok(file_size);
+        }
+
+        // BMP rows are stored bottom-up.
+        for (row, line) in image.chunks_exact_mut(row_size).rev().enumerate() {
+            let start = row * self.framebuffer_pitch as usize;
+
+            for (x, pixel) in line[..width * 3].chunks_exact_mut(3).enumerate() {
+                let value = self.read_pixel_value(start + x * self.bytes_per_pixel());
+                let (r, g, b) = self.decode_color(value);
+                pixel.copy_from_slice(&[b, g, r]);
+            }
+
+            line[width * 3..].fill(0);
+        }
+
+        Ok(file_size)
+    }
+
+    /// Returns a view of the framebuffer as a grid of pixels indexed by `(x, y)`, which hides
+    /// the pitch and the amount of bytes per pixel from the caller.
+    pub fn grid(&self) -> FramebufferGrid<'_> {
+        FramebufferGrid {
+            tag: self,
+            bytes_per_pixel: self.bytes_per_pixel(),
+        }
+    }
+
+    /// Returns an iterator yielding one mutable slice per row of the framebuffer, each of length
+    /// `width * bpp / 8`. The pitch may be larger than the width of a row, so consecutive
+    /// scanlines are not necessarily adjacent in memory.
+    ///
+    /// ## Safety
+    /// The same rules as for [StivaleFramebufferTag::as_mut_slice] apply.
+    pub unsafe fn scanlines_mut(&self) -> impl Iterator<Item = &mut [u8]> {
+        let addr = self.framebuffer_addr as usize;
+        let pitch = self.framebuffer_pitch as usize;
+        let row_len = self.framebuffer_width as usize * self.bytes_per_pixel();
+
+        (0..self.framebuffer_height as usize).map(move |row| {
+            core::slice::from_raw_parts_mut((addr + row * pitch) as *mut u8, row_len)
+        })
+    }
+}
+
+/// Wrapper around the framebuffer tag which represents the framebuffer as a drawing surface.
+pub struct Framebuffer<'a> {
+    tag: &'a StivaleFramebufferTag,
+}
+
+impl<'a> Framebuffer<'a> {
+    /// Creates a new framebuffer drawing surface from the provided framebuffer tag.
+    pub fn new(tag: &'a StivaleFramebufferTag) -> Self {
+        Self { tag }
+    }
+
+    /// Returns the framebuffer tag backing this drawing surface.
+    pub fn tag(&self) -> &'a StivaleFramebufferTag {
+        self.tag
+    }
+
+    /// Copies the provided back buffer into the framebuffer. The rows of the back buffer are
+    /// tightly packed, so it must be exactly `width * height * bpp / 8` bytes long, and they are
+    /// copied one by one to honor the pitch of the framebuffer.
+    ///
+    /// Returns [StivaleError::BufferLengthMismatch] if the back buffer has any other length.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u8; 8 * 2];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 1;
+    /// # tag.framebuffer_height = 2;
+    /// # tag.framebuffer_pitch = 8;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// let framebuffer = Framebuffer::new(&tag);
+    /// framebuffer.present(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    /// assert_eq!(framebuffer.present(&[0; 4]), Err(StivaleError::BufferLengthMismatch));
+    /// assert_eq!(buffer, [1, 2, 3, 4, 0, 0, 0, 0, 5, 6, 7, 8, 0, 0, 0, 0]);
+    /// ```
+    pub fn present(&self, back: &[u8]) -> Result<(), StivaleError> {
+        let row_len = self.tag.framebuffer_width as usize * self.tag.bytes_per_pixel();
+
+        if back.len() != row_len * self.tag.framebuffer_height as usize {
+            return Err(StivaleError::BufferLengthMismatch);
+        }
+
+        if row_len == 0 {
+            return Ok(());
+        }
+
+        for (row, scanline) in back
+            .chunks_exact(row_len)
+            .zip(unsafe { self.tag.scanlines_mut() })
+        {
+            scanline.copy_from_slice(row);
+        }
+
+        Ok(())
+    }
+}
+
+/// View of the framebuffer as a grid of pixels, returned by [StivaleFramebufferTag::grid].
+/// Pixels are addressed by their `(x, y)` coordinates and the stride of the rows is handled
+/// internally.
+pub struct FramebufferGrid<'a> {
+    tag: &'a StivaleFramebufferTag,
+    bytes_per_pixel: usize,
+}
+
+impl FramebufferGrid<'_> {
+    /// Returns the width of the grid in pixels.
+    pub fn width(&self) -> u16 {
+        self.tag.framebuffer_width
+    }
+
+    /// Returns the height of the grid in pixels.
+    pub fn height(&self) -> u16 {
+        self.tag.framebuffer_height
+    }
+
+    /// Returns the byte offset of the pixel at the provided coordinates.
+    fn offset(&self, x: u16, y: u16) -> Result<usize, StivaleError> {
+        self.tag.check_memory_model()?;
+
+        if x >= self.tag.framebuffer_width || y >= self.tag.framebuffer_height {
+            return Err(StivaleError::OutOfBounds);
+        }
+
+        Ok(y as usize * self.tag.framebuffer_pitch as usize + x as usize * self.bytes_per_pixel)
+    }
+
+    /// Sets the pixel at the provided coordinates to the provided RGB color.
+    ///
+    /// Returns [StivaleError::OutOfBounds] if the coordinates are out of bounds and
+    /// [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer is not RGB.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut buffer = [0u8; 4 * 4 * 2];
+    /// # let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// # tag.framebuffer_addr = buffer.as_mut_ptr() as u64;
+    /// # tag.framebuffer_width = 3;
+    /// # tag.framebuffer_height = 2;
+    /// # tag.framebuffer_pitch = 16;
+    /// # tag.framebuffer_bpp = 32;
+    /// # tag.memory_model = 1;
+    /// # (tag.red_mask_size, tag.red_mask_shift) = (8, 16);
+    /// # (tag.green_mask_size, tag.green_mask_shift) = (8, 8);
+    /// # (tag.blue_mask_size, tag.blue_mask_shift) = (8, 0);
+    ///
+    /// let grid = tag.grid();
+    /// grid.set(2, 1, (0x12, 0x34, 0x56)).unwrap();
+    /// assert_eq!(grid.get(2, 1), Ok((0x12, 0x34, 0x56)));
+    /// assert_eq!(grid.set(3, 0, (0, 0, 0)), Err(StivaleError::OutOfBounds));
+    /// assert_eq!(grid.get(0, 2), Err(StivaleError::OutOfBounds));
+    /// assert_eq!(buffer[16 + 8..16 + 12], [0x56, 0x34, 0x12, 0x00]);
+    /// ```
+    pub fn set(&self, x: u16, y: u16, rgb: (u8, u8, u8)) -> Result<(), StivaleError> {
+        let offset = self.offset(x, y)?;
+        self.tag
+            .write_pixel_value(offset, self.tag.encode_color(rgb.0, rgb.1, rgb.2));
+        Ok(())
+    }
+
+    /// Returns the RGB color of the pixel at the provided coordinates.
+    ///
+    /// Returns [StivaleError::OutOfBounds] if the coordinates are out of bounds and
+    /// [StivaleError::UnsupportedMemoryModel] if the memory model of the framebuffer is not RGB.
+    pub fn get(&self, x: u16, y: u16) -> Result<(u8, u8, u8), StivaleError> {
+        let offset = self.offset(x, y)?;
+        Ok(self.tag.decode_color(self.tag.read_pixel_value(offset)))
+    }
+}
+
+#[cfg(feature = "embedded-graphics")]
+mod embedded_graphics {
+    use core::convert::TryFrom;
+
+    use embedded_graphics_core::draw_target::DrawTarget;
+    use embedded_graphics_core::geometry::{OriginDimensions, Size};
+    use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
+    use embedded_graphics_core::Pixel;
+
+    use super::{Framebuffer, StivaleError};
+
+    impl OriginDimensions for Framebuffer<'_> {
+        fn size(&self) -> Size {
+            Size::new(
+                self.tag.framebuffer_width as u32,
+                self.tag.framebuffer_height as u32,
+            )
+        }
+    }
+
+    impl DrawTarget for Framebuffer<'_> {
+        type Color = Rgb888;
+        type Error = StivaleError;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(point, color) in pixels {
+                // Pixels outside of the framebuffer are clipped by `put_pixel`.
+                if let (Ok(x), Ok(y)) = (u16::try_from(point.x), u16::try_from(point.y)) {
+                    self.tag
+                        .put_pixel(x, y, (color.r(), color.g(), color.b()))?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
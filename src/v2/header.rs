@@ -1,3 +1,5 @@
+use super::header_builder::StivaleHeaderTag;
+use super::ident::*;
 use super::StivaleStruct;
 
 macro_rules! make_header_tag {
@@ -14,13 +16,23 @@ macro_rules! make_header_tag {
 			$($field_name: $field_ty),*
 		}
 
+        #[allow(deprecated)] unsafe impl StivaleHeaderTag for $name {
+			const IDENTIFIER: u64 = $id;
+		}
+
         #[allow(deprecated)] unsafe impl Send for $name {}
         #[allow(deprecated)] unsafe impl Sync for $name {}
 
+        #[allow(deprecated)] impl Default for $name {
+			fn default() -> Self {
+				Self::new()
+			}
+		}
+
         #[allow(deprecated)] impl $name {
 			pub const fn new() -> Self {
 				Self {
-					identifier: $id,
+					identifier: <Self as StivaleHeaderTag>::IDENTIFIER,
 					next: core::ptr::null(),
 					$($field_name: $field_default),*
 				}
@@ -52,6 +64,12 @@ pub struct StivaleHeader {
     tags: *const (),
 }
 
+impl Default for StivaleHeader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StivaleHeader {
     pub const fn new() -> Self {
         Self {
@@ -97,7 +115,7 @@ make_header_tag!(
     /// If this tag is present the bootloader is instructed to initialise a graphical
     /// framebuffer video mode. Omitting this tag will make the bootloader default to a
     /// CGA-compatible text mode, if supported.
-    struct StivaleFramebufferHeaderTag: 0x3ecc1bc43d0f7971 => {
+    struct StivaleFramebufferHeaderTag: STIVALE2_HEADER_TAG_FRAMEBUFFER_ID => {
         framebuffer_width: u16 = 0,
         framebuffer_height: u16 = 0,
         framebuffer_bpp: u16 = 0,
@@ -110,8 +128,13 @@ make_header_tag!(
     /// for use by the kernel at runtime. See "Terminal struct tag" below. The framebuffer
     /// header tag **must** be specified when passing this header tag, and this tag may inhibit
     /// the WC MTRR framebuffer feature.
-    struct StivaleTerminalHeaderTag: 0xa85d499b1823be72 => {
-        flags: u64 = 0
+    struct StivaleTerminalHeaderTag: STIVALE2_HEADER_TAG_TERMINAL_ID => {
+        /// Bit 0: set if a callback is provided.
+        flags: u64 = 0,
+        /// The callback invoked by the terminal for events such as bell and DEC private modes,
+        /// see the `STIVALE2_TERM_CB_*` constants for the possible callback types. It is only used
+        /// if bit 0 of the flags is set.
+        callback: Option<extern "C" fn(u64, u64, u64, u64)> = None
     };
 );
 
@@ -126,7 +149,7 @@ bitflags::bitflags! {
 }
 
 make_header_tag!(
-    struct StivaleSmpHeaderTag: 0x1ab015085f3273df => {
+    struct StivaleSmpHeaderTag: STIVALE2_HEADER_TAG_SMP_ID => {
         flags: StivaleSmpHeaderTagFlags = StivaleSmpHeaderTagFlags::XAPIC
     };
 );
@@ -139,19 +162,22 @@ make_header_tag!(
     /// ## Legacy
     /// This tag is deprecated and considered legacy. Use is discouraged and it may not be supported on newer bootloaders.
     #[deprecated(note = "This tag is deprecated and considered legacy. Use is discouraged and it may not be supported on newer bootloaders.")]
-    struct StivaleMtrrHeaderTag: 0x4c7bb07731282e00;
+    struct StivaleMtrrHeaderTag: STIVALE2_HEADER_TAG_FB_MTRR_ID;
 );
 
 make_header_tag!(
     /// If this tag is present the bootloader is instructed to enable upport for 5-level paging, if
     /// available.
-    struct Stivale5LevelPagingHeaderTag: 0x932f477032007e8f;
+    ///
+    /// The bootloader does not report whether 5-level paging was enabled through a struct tag, so
+    /// the kernel must read the LA57 bit of CR4 itself to know which paging mode is active.
+    struct Stivale5LevelPagingHeaderTag: STIVALE2_HEADER_TAG_5LV_PAGING_ID;
 );
 
 make_header_tag!(
     /// If this tag is present the bootloader is instructed to unmap the first page of the virtual address
     /// space before passing control to the kernel, for architectures that support paging.
-    struct StivaleUnmapNullHeaderTag: 0x92919432b16fe7e7;
+    struct StivaleUnmapNullHeaderTag: STIVALE2_HEADER_TAG_UNMAP_NULL_ID;
 );
 
 make_header_tag!(
@@ -159,7 +185,7 @@ make_header_tag!(
     /// to be initialised. Omitting both the any video header tag and the framebuffer header
     /// tag means "force CGA text mode" (where available), and the bootloader will refuse to
     /// boot the kernel if it fails to fulfill that request.
-    struct StivaleAnyVideoTag: 0xc75c9fa92a44c4db => {
+    struct StivaleAnyVideoTag: STIVALE2_HEADER_TAG_ANY_VIDEO_ID => {
         /// 0: prefer linear framebuffer
         ///
         /// 1: prefer no linear framebuffer
@@ -172,3 +198,21 @@ make_header_tag!(
 
 unsafe impl Send for StivaleHeader {}
 unsafe impl Sync for StivaleHeader {}
+
+// Compile-time checks that the header and the header tags have the sizes mandated by the
+// stivale2 specification, so a wrong field type cannot silently produce a malformed header. The
+// pointer fields are only 64 bits wide on 64-bit targets.
+#[cfg(target_pointer_width = "64")]
+#[allow(deprecated)]
+const _: () = {
+    use core::mem::size_of;
+
+    assert!(size_of::<StivaleHeader>() == 32);
+    assert!(size_of::<StivaleFramebufferHeaderTag>() == 24);
+    assert!(size_of::<StivaleTerminalHeaderTag>() == 32);
+    assert!(size_of::<StivaleSmpHeaderTag>() == 24);
+    assert!(size_of::<StivaleMtrrHeaderTag>() == 16);
+    assert!(size_of::<Stivale5LevelPagingHeaderTag>() == 16);
+    assert!(size_of::<StivaleUnmapNullHeaderTag>() == 16);
+    assert!(size_of::<StivaleAnyVideoTag>() == 24);
+};
@@ -0,0 +1,55 @@
+//! This module contains helpers to declare the linked list of stivale2 header tags at compile
+//! time.
+
+/// Trait implemented by every stivale2 header tag, associating the tag with its identifier.
+///
+/// ## Safety
+/// The implementing type must have the exact memory layout of the header tag described by
+/// [StivaleHeaderTag::IDENTIFIER] in the stivale2 specification.
+pub unsafe trait StivaleHeaderTag {
+    /// The identifier of this header tag as specified by the stivale2 specification.
+    const IDENTIFIER: u64;
+}
+
+/// Declares a list of header tag statics which are linked together at compile time, in the order
+/// they are declared. Each tag's `next` pointer is set to the tag declared after it, and the last
+/// tag's `next` pointer is left as is. The first tag is the head of the list, which should be
+/// passed to [crate::v2::StivaleHeader::tags].
+///
+/// ## Example
+/// ```rust
+/// use stivale_boot::stivale2_header_tags;
+/// use stivale_boot::v2::*;
+///
+/// stivale2_header_tags! {
+///     static TERMINAL_TAG: StivaleTerminalHeaderTag = StivaleTerminalHeaderTag::new();
+///     static FRAMEBUFFER_TAG: StivaleFramebufferHeaderTag =
+///         StivaleFramebufferHeaderTag::new().framebuffer_bpp(24);
+/// }
+///
+/// #[link_section = ".stivale2hdr"]
+/// #[used]
+/// static STIVALE_HDR: StivaleHeader =
+///     StivaleHeader::new().tags(&TERMINAL_TAG as *const StivaleTerminalHeaderTag as *const ());
+/// ```
+#[macro_export]
+macro_rules! stivale2_header_tags {
+    () => {};
+
+    (
+        $(#[$meta:meta])* $vis:vis static $name:ident: $ty:ty = $init:expr;
+        $(#[$next_meta:meta])* $next_vis:vis static $next:ident: $next_ty:ty = $next_init:expr;
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])* $vis static $name: $ty = $init.next(&$next as *const $next_ty as *const ());
+
+        $crate::stivale2_header_tags!(
+            $(#[$next_meta])* $next_vis static $next: $next_ty = $next_init;
+            $($rest)*
+        );
+    };
+
+    ($(#[$meta:meta])* $vis:vis static $name:ident: $ty:ty = $init:expr;) => {
+        $(#[$meta])* $vis static $name: $ty = $init;
+    };
+}
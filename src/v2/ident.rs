@@ -0,0 +1,48 @@
+//! This module contains the identifiers of the stivale2 header tags and struct tags as
+//! specified by the stivale2 specification.
+//!
+//! ## Example
+//! ```rust
+//! use stivale_boot::v2::*;
+//!
+//! assert_eq!(STIVALE2_STRUCT_TAG_FRAMEBUFFER_ID, 0x506461d2950408fa);
+//! assert_eq!(STIVALE2_STRUCT_TAG_MEMMAP_ID, 0x2187f79e8612de07);
+//! assert_eq!(STIVALE2_HEADER_TAG_TERMINAL_ID, 0xa85d499b1823be72);
+//! assert_eq!(StivaleFramebufferTag::IDENTIFIER, STIVALE2_STRUCT_TAG_FRAMEBUFFER_ID);
+//! ```
+
+pub const STIVALE2_HEADER_TAG_ANY_VIDEO_ID: u64 = 0xc75c9fa92a44c4db;
+pub const STIVALE2_HEADER_TAG_FRAMEBUFFER_ID: u64 = 0x3ecc1bc43d0f7971;
+pub const STIVALE2_HEADER_TAG_FB_MTRR_ID: u64 = 0x4c7bb07731282e00;
+pub const STIVALE2_HEADER_TAG_TERMINAL_ID: u64 = 0xa85d499b1823be72;
+pub const STIVALE2_HEADER_TAG_SMP_ID: u64 = 0x1ab015085f3273df;
+pub const STIVALE2_HEADER_TAG_5LV_PAGING_ID: u64 = 0x932f477032007e8f;
+pub const STIVALE2_HEADER_TAG_UNMAP_NULL_ID: u64 = 0x92919432b16fe7e7;
+
+pub const STIVALE2_STRUCT_TAG_PMRS_ID: u64 = 0x5df266a64047b6bd;
+pub const STIVALE2_STRUCT_TAG_KERNEL_BASE_ADDRESS_ID: u64 = 0x060d78874a2a8af0;
+pub const STIVALE2_STRUCT_TAG_CMDLINE_ID: u64 = 0xe5e76a1b4597a781;
+pub const STIVALE2_STRUCT_TAG_MEMMAP_ID: u64 = 0x2187f79e8612de07;
+pub const STIVALE2_STRUCT_TAG_FRAMEBUFFER_ID: u64 = 0x506461d2950408fa;
+pub const STIVALE2_STRUCT_TAG_EDID_ID: u64 = 0x968609d7af96b845;
+pub const STIVALE2_STRUCT_TAG_TEXTMODE_ID: u64 = 0x38d74c23e0dca893;
+pub const STIVALE2_STRUCT_TAG_FB_MTRR_ID: u64 = 0x6bc1a78ebe871172;
+pub const STIVALE2_STRUCT_TAG_TERMINAL_ID: u64 = 0xc2b3f4c3233b0974;
+pub const STIVALE2_STRUCT_TAG_MODULES_ID: u64 = 0x4b6fe466aade04ce;
+pub const STIVALE2_STRUCT_TAG_RSDP_ID: u64 = 0x9e1786930a375e78;
+pub const STIVALE2_STRUCT_TAG_SMBIOS_ID: u64 = 0x274bd246c62bf7d1;
+pub const STIVALE2_STRUCT_TAG_EPOCH_ID: u64 = 0x566a7bed888e1407;
+pub const STIVALE2_STRUCT_TAG_FIRMWARE_ID: u64 = 0x359d837855e3858c;
+pub const STIVALE2_STRUCT_TAG_EFI_SYSTEM_TABLE_ID: u64 = 0x4bc5ec15845b558e;
+pub const STIVALE2_STRUCT_TAG_KERNEL_FILE_ID: u64 = 0xe599d90c2975584a;
+pub const STIVALE2_STRUCT_TAG_KERNEL_FILE_V2_ID: u64 = 0x37c13018a02c6ea2;
+pub const STIVALE2_STRUCT_TAG_BOOT_VOLUME_ID: u64 = 0x9b4358364c19ee62;
+pub const STIVALE2_STRUCT_TAG_KERNEL_SLIDE_ID: u64 = 0xee80847d01506c57;
+pub const STIVALE2_STRUCT_TAG_SMP_ID: u64 = 0x34d1d96339647025;
+pub const STIVALE2_STRUCT_TAG_PXE_SERVER_INFO_ID: u64 = 0x29d1e96239247032;
+pub const STIVALE2_STRUCT_TAG_MMIO32_UART_ID: u64 = 0xb813f9b8dbc78797;
+pub const STIVALE2_STRUCT_TAG_DTB_ID: u64 = 0xabb29bd49a2833fa;
+/// The HHDM tag was formerly known as the VMAP tag and shares its identifier.
+pub const STIVALE2_STRUCT_TAG_HHDM_ID: u64 = 0xb0ed257db18cb58f;
+/// Legacy name of [STIVALE2_STRUCT_TAG_HHDM_ID].
+pub const STIVALE2_STRUCT_TAG_VMAP_ID: u64 = STIVALE2_STRUCT_TAG_HHDM_ID;
@@ -0,0 +1,137 @@
+//! This module contains the helpers of the stivale2 kernel file tags.
+
+use super::tag::StivaleKernelFileV2Tag;
+use super::utils;
+
+/// The magic bytes at the start of an ELF file.
+const ELF_MAGIC: [u8; 4] = *b"\x7fELF";
+/// `EI_CLASS` value of 64-bit ELF files.
+const ELF_CLASS_64: u8 = 2;
+/// `EI_DATA` value of little endian ELF files.
+const ELF_DATA_LE: u8 = 1;
+/// Section type of sections which occupy no space in the file, such as `.bss`.
+const SHT_NOBITS: u32 = 8;
+
+/// Reads the little endian `N` byte value at the provided offset of the image.
+fn read_le<const N: usize>(image: &[u8], offset: usize) -> Option<[u8; N]> {
+    let bytes = image.get(offset..offset.checked_add(N)?)?;
+    let mut value = [0; N];
+    value.copy_from_slice(bytes);
+    Some(value)
+}
+
+/// Reads the little endian `u16` at the provided offset of the image.
+fn read_u16(image: &[u8], offset: usize) -> Option<u16> {
+    read_le(image, offset).map(u16::from_le_bytes)
+}
+
+/// Reads the little endian `u32` at the provided offset of the image.
+fn read_u32(image: &[u8], offset: usize) -> Option<u32> {
+    read_le(image, offset).map(u32::from_le_bytes)
+}
+
+/// Reads the little endian `u64` offset or size at the provided offset of the image.
+fn read_offset(image: &[u8], offset: usize) -> Option<usize> {
+    read_le(image, offset).map(|bytes| u64::from_le_bytes(bytes) as usize)
+}
+
+/// Returns the bytes of the range of the image with the provided offset and size.
+fn range(image: &[u8], offset: usize, size: usize) -> Option<&[u8]> {
+    image.get(offset..offset.checked_add(size)?)
+}
+
+/// Returns the contents of the section with the provided name in the little endian ELF64 image.
+fn find_section<'a>(image: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    let ident = image.get(..6)?;
+    if ident[..4] != ELF_MAGIC || ident[4] != ELF_CLASS_64 || ident[5] != ELF_DATA_LE {
+        return None;
+    }
+
+    let shoff = read_offset(image, 0x28)?;
+    let shentsize = read_u16(image, 0x3a)? as usize;
+    let shnum = read_u16(image, 0x3c)? as usize;
+    let shstrndx = read_u16(image, 0x3e)? as usize;
+
+    // Every field is read from the bounded section header, so a corrupt section header offset
+    // cannot overflow.
+    let section = |index: usize| {
+        let start = shoff.checked_add(index.checked_mul(shentsize)?)?;
+        let header = range(image, start, shentsize)?;
+
+        let name_offset = read_u32(header, 0x0)? as usize;
+        let ty = read_u32(header, 0x4)?;
+        let offset = read_offset(header, 0x18)?;
+        let size = read_offset(header, 0x20)?;
+        Some((name_offset, ty, offset, size))
+    };
+
+    let (_, _, strtab_offset, strtab_size) = section(shstrndx)?;
+    let strtab = range(image, strtab_offset, strtab_size)?;
+
+    (0..shnum).find_map(|index| {
+        let (name_offset, ty, offset, size) = section(index)?;
+
+        if utils::str_from_slice_checked(strtab.get(name_offset..)?) != Ok(name) {
+            return None;
+        }
+
+        if ty == SHT_NOBITS {
+            Some(&[][..])
+        } else {
+            range(image, offset, size)
+        }
+    })
+}
+
+impl StivaleKernelFileV2Tag {
+    /// Returns the contents of the section with the provided name in the raw kernel file, such
+    /// as `.ksyms` or `.symtab`. Returns `None` if the kernel file is not a little endian ELF64
+    /// file, if there is no such section or if it lies outside of the kernel file. Sections
+    /// which occupy no space in the file, such as `.bss`, are returned as an empty slice.
+    ///
+    /// This only reads the ELF header, the section headers and the section header string table,
+    /// and every read is bounded by the size of the kernel file.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut image = [0u8; 0x1c0];
+    /// let mut put = |offset: usize, bytes: &[u8]| {
+    ///     image[offset..offset + bytes.len()].copy_from_slice(bytes)
+    /// };
+    ///
+    /// // The ELF header, with 3 section headers at 0x100 and the string table in section 2.
+    /// put(0, b"\x7fELF\x02\x01\x01");
+    /// put(0x28, &0x100u64.to_le_bytes());
+    /// put(0x3a, &[64, 0, 3, 0, 2, 0]);
+    ///
+    /// put(0x40, b"hello");
+    /// put(0x50, b"\0.ksyms\0.shstrtab\0");
+    ///
+    /// // The section headers, after the null section header.
+    /// for (index, (name, ty, offset, size)) in [(1u32, 1u32, 0x40u64, 5u64), (8, 3, 0x50, 18)].iter().enumerate() {
+    ///     let header = 0x100 + (index + 1) * 64;
+    ///     put(header, &name.to_le_bytes());
+    ///     put(header + 0x4, &ty.to_le_bytes());
+    ///     put(header + 0x18, &offset.to_le_bytes());
+    ///     put(header + 0x20, &size.to_le_bytes());
+    /// }
+    ///
+    /// let tag = StivaleKernelFileV2Tag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_KERNEL_FILE_V2_ID, next: 0 },
+    ///     kernel_start: image.as_ptr() as u64,
+    ///     kernel_size: image.len() as u64,
+    /// };
+    ///
+    /// assert_eq!(tag.kernel_section(".ksyms"), Some(&b"hello"[..]));
+    /// assert_eq!(tag.kernel_section(".text"), None);
+    ///
+    /// // A corrupt section header offset is rejected.
+    /// image[0x28..0x30].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+    /// let tag = StivaleKernelFileV2Tag { kernel_start: image.as_ptr() as u64, ..tag };
+    /// assert_eq!(tag.kernel_section(".ksyms"), None);
+    /// ```
+    pub fn kernel_section(&self, name: &str) -> Option<&[u8]> {
+        find_section(self.as_bytes(), name)
+    }
+}
@@ -0,0 +1,570 @@
+//! This module contains the helpers of the stivale2 memory map tag.
+
+use core::fmt;
+use core::iter::Peekable;
+use core::ops::Index;
+use core::slice;
+
+use super::error::MemoryMapError;
+use super::tag::{
+    StivaleMemoryMapEntry, StivaleMemoryMapEntryType, StivaleMemoryMapIter, StivaleMemoryMapTag,
+};
+
+/// The size of a physical frame, which usable entries are guaranteed to be aligned to.
+const FRAME_SIZE: u64 = 0x1000;
+
+impl StivaleMemoryMapEntryType {
+    /// Converts a raw memory map entry type into a [StivaleMemoryMapEntryType], returning `None`
+    /// if the value is not a type defined by the stivale2 specification.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::StivaleMemoryMapEntryType;
+    /// assert_eq!(StivaleMemoryMapEntryType::from_u32(0x1000), Some(StivaleMemoryMapEntryType::BootloaderReclaimable));
+    /// assert_eq!(StivaleMemoryMapEntryType::from_u32(6), None);
+    /// ```
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(Self::Usable),
+            2 => Some(Self::Reserved),
+            3 => Some(Self::AcpiReclaimable),
+            4 => Some(Self::AcpiNvs),
+            5 => Some(Self::BadMemory),
+            0x1000 => Some(Self::BootloaderReclaimable),
+            0x1001 => Some(Self::Kernel),
+            0x1002 => Some(Self::Framebuffer),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StivaleMemoryMapEntryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `pad` is used so width and alignment flags are honored when printing tables.
+        f.pad(match self {
+            Self::Usable => "Usable",
+            Self::Reserved => "Reserved",
+            Self::AcpiReclaimable => "ACPI Reclaimable",
+            Self::AcpiNvs => "ACPI NVS",
+            Self::BadMemory => "Bad Memory",
+            Self::BootloaderReclaimable => "Bootloader Reclaimable",
+            Self::Kernel => "Kernel",
+            Self::Framebuffer => "Framebuffer",
+        })
+    }
+}
+
+/// Splits a size in bytes into a value and a unit suitable for printing, using MiB for sizes of
+/// at least 1 MiB and KiB otherwise.
+fn size_with_unit(bytes: u64) -> (u64, &'static str) {
+    if bytes >= 1024 * 1024 {
+        (bytes / (1024 * 1024), "MiB")
+    } else {
+        (bytes / 1024, "KiB")
+    }
+}
+
+/// Removes the hole `[hole_base, hole_base + hole_len)` from the memory region described by the
+/// provided entry, returning the `(base, length)` pairs of the up to two fragments that are left,
+/// lowest first. The fragments are stored from the start of the array, so the second fragment is
+/// only present if the hole lies strictly inside the region.
+///
+/// ## Example
+/// ```rust
+/// # use stivale_boot::v2::*;
+/// let entry = StivaleMemoryMapEntry::new(0x1000, 0x4000, StivaleMemoryMapEntryType::Usable);
+///
+/// // The hole is fully inside the region.
+/// assert_eq!(subtract(&entry, 0x2000, 0x1000), [Some((0x1000, 0x1000)), Some((0x3000, 0x2000))]);
+/// // The hole overlaps the start or the end of the region.
+/// assert_eq!(subtract(&entry, 0x0, 0x2000), [Some((0x2000, 0x3000)), None]);
+/// assert_eq!(subtract(&entry, 0x4000, 0x2000), [Some((0x1000, 0x3000)), None]);
+/// // The hole covers the whole region.
+/// assert_eq!(subtract(&entry, 0x1000, 0x4000), [None, None]);
+/// assert_eq!(subtract(&entry, 0x0, u64::MAX), [None, None]);
+/// // The hole does not overlap with the region.
+/// assert_eq!(subtract(&entry, 0x5000, 0x1000), [Some((0x1000, 0x4000)), None]);
+/// assert_eq!(subtract(&entry, 0x0, 0x1000), [Some((0x1000, 0x4000)), None]);
+/// assert_eq!(subtract(&entry, 0x2000, 0), [Some((0x1000, 0x4000)), None]);
+/// ```
+pub fn subtract(
+    entry: &StivaleMemoryMapEntry,
+    hole_base: u64,
+    hole_len: u64,
+) -> [Option<(u64, u64)>; 2] {
+    let base = entry.base;
+    let end = base.saturating_add(entry.length);
+    let hole_end = hole_base.saturating_add(hole_len);
+
+    if hole_base >= end || hole_end <= base || hole_len == 0 {
+        return [Some((base, end - base)), None];
+    }
+
+    let below = Some((base, hole_base.saturating_sub(base))).filter(|&(_, len)| len > 0);
+    let above = Some((hole_end, end.saturating_sub(hole_end))).filter(|&(_, len)| len > 0);
+
+    match (below, above) {
+        (None, above) => [above, None],
+        (below, above) => [below, above],
+    }
+}
+
+/// Iterator merging adjacent usable or bootloader reclaimable entries of the same type.
+struct CoalescedIter<'a> {
+    entries: Peekable<slice::Iter<'a, StivaleMemoryMapEntry>>,
+}
+
+impl<'a> Iterator for CoalescedIter<'a> {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.entries.find(|entry| {
+            matches!(
+                entry.entry_type(),
+                StivaleMemoryMapEntryType::Usable
+                    | StivaleMemoryMapEntryType::BootloaderReclaimable
+            )
+        })?;
+
+        let ty = first.entry_type();
+        let base = first.base;
+        let mut end = first.end_address();
+
+        while let Some(entry) = self
+            .entries
+            .next_if(|entry| entry.entry_type() == ty && entry.base == end)
+        {
+            end = entry.end_address();
+        }
+
+        Some((base, end - base))
+    }
+}
+
+impl StivaleMemoryMapTag {
+    /// Returns the memory map entry at the provided index, or `None` if the index is out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<&StivaleMemoryMapEntry> {
+        self.as_slice().get(index)
+    }
+
+    /// Returns an iterator over the memory map entries of the provided type.
+    pub fn entries_of_type(
+        &self,
+        ty: StivaleMemoryMapEntryType,
+    ) -> impl Iterator<Item = &StivaleMemoryMapEntry> {
+        self.iter().filter(move |entry| entry.entry_type() == ty)
+    }
+
+    /// Checks that the memory map upholds the guarantees of the stivale2 specification: the
+    /// entries are sorted by base address, the usable and bootloader reclaimable entries are
+    /// 4096 byte aligned in base and length, and these entries do not overlap any other entry.
+    ///
+    /// Returns an error containing the index of the first entry violating one of them.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 3],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 3,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x2000, StivaleMemoryMapEntryType::Reserved),
+    /// #         StivaleMemoryMapEntry::new(0x1000, 0x3000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x4000, 0x1800, StivaleMemoryMapEntryType::Usable),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 3) };
+    /// // The first usable entry overlaps the reserved entry.
+    /// assert_eq!(memory_map.verify(), Err(MemoryMapError::Overlapping(1)));
+    /// ```
+    pub fn verify(&self) -> Result<(), MemoryMapError> {
+        let is_page_aligned = |entry: &StivaleMemoryMapEntry| {
+            entry.base.is_multiple_of(FRAME_SIZE) && entry.length.is_multiple_of(FRAME_SIZE)
+        };
+
+        let mut previous_base = 0;
+        // The highest end address of all the previous entries and of the previous usable and
+        // bootloader reclaimable entries.
+        let mut end = 0;
+        let mut usable_end = 0;
+
+        for (index, entry) in self.iter().enumerate() {
+            let usable = matches!(
+                entry.entry_type(),
+                StivaleMemoryMapEntryType::Usable
+                    | StivaleMemoryMapEntryType::BootloaderReclaimable
+            );
+
+            if entry.base < previous_base {
+                return Err(MemoryMapError::Unsorted(index));
+            }
+
+            if usable && !is_page_aligned(entry) {
+                return Err(MemoryMapError::Misaligned(index));
+            }
+
+            if (usable && end > entry.base) || usable_end > entry.base {
+                return Err(MemoryMapError::Overlapping(index));
+            }
+
+            let entry_end = entry.base.saturating_add(entry.length);
+
+            previous_base = entry.base;
+            end = end.max(entry_end);
+            if usable {
+                usable_end = usable_end.max(entry_end);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the total amount of usable memory in bytes.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 3],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 3,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x1000, 0x2000, StivaleMemoryMapEntryType::Reserved),
+    /// #         StivaleMemoryMapEntry::new(0x3000, 0x4000, StivaleMemoryMapEntryType::Usable),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 3) };
+    /// assert_eq!(memory_map.usable_memory(), 0x5000);
+    /// assert_eq!(memory_map.total_memory(), 0x7000);
+    /// ```
+    pub fn usable_memory(&self) -> u64 {
+        self.entries_of_type(StivaleMemoryMapEntryType::Usable)
+            .fold(0, |total, entry| total.saturating_add(entry.length))
+    }
+
+    /// Returns the total amount of memory described by the memory map in bytes, regardless
+    /// of the entry types.
+    pub fn total_memory(&self) -> u64 {
+        self.iter()
+            .fold(0, |total, entry| total.saturating_add(entry.length))
+    }
+
+    /// Returns the largest usable memory region. If several regions have the same length,
+    /// the first one is returned.
+    pub fn largest_usable_region(&self) -> Option<&StivaleMemoryMapEntry> {
+        self.entries_of_type(StivaleMemoryMapEntryType::Usable)
+            .fold(
+                None,
+                |largest: Option<&StivaleMemoryMapEntry>, entry| match largest {
+                    Some(largest) if largest.length >= entry.length => Some(largest),
+                    _ => Some(entry),
+                },
+            )
+    }
+
+    /// Returns an iterator over the base addresses of every 4 KiB frame in the usable memory
+    /// regions. Usable entries are guaranteed to be 4096 byte aligned for both base and length.
+    pub fn usable_frames(&self) -> impl Iterator<Item = u64> + '_ {
+        self.entries_of_type(StivaleMemoryMapEntryType::Usable)
+            .flat_map(|entry| (entry.base..entry.end_address()).step_by(FRAME_SIZE as usize))
+    }
+
+    /// Returns an iterator over the usable memory map entries which end above the provided
+    /// physical address, such as for allocations that must avoid the legacy ISA DMA range.
+    ///
+    /// The yielded entries are the original entries and are not clipped, so an entry may still
+    /// start below `min`. Only the part of such an entry from `min` to its end address should be
+    /// used.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 3],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 3,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x9_f000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0xf_0000, 0x20_0000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x30_0000, 0x1000, StivaleMemoryMapEntryType::Reserved),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 3) };
+    /// const ONE_MIB: u64 = 0x10_0000;
+    ///
+    /// let mut regions = memory_map.usable_above(ONE_MIB);
+    /// let region = regions.next().unwrap();
+    ///
+    /// // The entry starts below 1 MiB, only the part above it should be used.
+    /// assert_eq!(region.base, 0xf_0000);
+    /// assert_eq!(region.end_address() - region.base.max(ONE_MIB), 0x1f_0000);
+    /// assert!(regions.next().is_none());
+    /// ```
+    pub fn usable_above(&self, min: u64) -> impl Iterator<Item = &StivaleMemoryMapEntry> {
+        self.entries_of_type(StivaleMemoryMapEntryType::Usable)
+            .filter(move |entry| entry.end_address() > min)
+    }
+
+    /// Returns an iterator over the `(base, length)` pairs of the usable and bootloader
+    /// reclaimable memory regions, where adjacent entries of the same type are merged together.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 4],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 4,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x1000, 0x2000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x3000, 0x1000, StivaleMemoryMapEntryType::Reserved),
+    /// #         StivaleMemoryMapEntry::new(0x4000, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 4) };
+    /// let mut regions = memory_map.coalesced();
+    ///
+    /// assert_eq!(regions.next(), Some((0x0, 0x3000)));
+    /// assert_eq!(regions.next(), Some((0x4000, 0x1000)));
+    /// assert_eq!(regions.next(), None);
+    /// ```
+    pub fn coalesced(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        CoalescedIter {
+            entries: self.as_slice().iter().peekable(),
+        }
+    }
+
+    /// Collects the regions yielded by [StivaleMemoryMapTag::coalesced] into a vector of
+    /// `(base, length)` pairs, such as for initializing a physical memory allocator.
+    ///
+    /// The vector holds at most `N` regions, any further regions are dropped. Comparing the
+    /// length of the vector with `coalesced().count()` tells whether regions were dropped.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 3],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 3,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x1000, 0x1000, StivaleMemoryMapEntryType::Reserved),
+    /// #         StivaleMemoryMapEntry::new(0x2000, 0x1000, StivaleMemoryMapEntryType::BootloaderReclaimable),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 3) };
+    /// let free = memory_map.free_list::<1>();
+    ///
+    /// assert_eq!(free, [(0x0, 0x1000)]);
+    /// assert_eq!(memory_map.coalesced().count() - free.len(), 1);
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn free_list<const N: usize>(&self) -> heapless::Vec<(u64, u64), N> {
+        self.coalesced().take(N).collect()
+    }
+
+    /// Returns an iterator over the `(base, length)` pairs of the bootloader reclaimable memory
+    /// regions, which can be freed once the kernel no longer needs anything the bootloader left
+    /// in them.
+    ///
+    /// ## Safety
+    /// The stivale2 struct and its tags, the SMP info array, the module metadata and strings,
+    /// the command line and the terminal and its write function may all live in these regions.
+    /// Everything still needed, such as the memory map itself (see
+    /// [StivaleMemoryMapTag::copy_into]), must be copied before reclaiming them, and the terminal
+    /// must not be used anymore.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 4],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 4,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x1000, 0x2000, StivaleMemoryMapEntryType::BootloaderReclaimable),
+    /// #         StivaleMemoryMapEntry::new(0x3000, 0x1000, StivaleMemoryMapEntryType::AcpiReclaimable),
+    /// #         StivaleMemoryMapEntry::new(0x4000, 0x1000, StivaleMemoryMapEntryType::BootloaderReclaimable),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 4) };
+    /// let mut regions = memory_map.reclaimable_regions();
+    ///
+    /// assert_eq!(regions.next(), Some((0x1000, 0x2000)));
+    /// assert_eq!(regions.next(), Some((0x4000, 0x1000)));
+    /// assert_eq!(regions.next(), None);
+    /// ```
+    pub fn reclaimable_regions(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.entries_of_type(StivaleMemoryMapEntryType::BootloaderReclaimable)
+            .map(|entry| (entry.base, entry.length))
+    }
+
+    /// Returns the memory map entry containing the provided physical address, or `None` if the
+    /// address lies in a gap of the memory map.
+    ///
+    /// As the entries are guaranteed to be sorted by base address, this performs a binary search
+    /// for the last entry starting at or below the address.
+    pub fn region_for(&self, addr: u64) -> Option<&StivaleMemoryMapEntry> {
+        let entries = self.as_slice();
+        let index = entries.partition_point(|entry| entry.base <= addr);
+
+        entries[..index]
+            .last()
+            .filter(|entry| addr - entry.base < entry.length)
+    }
+
+    /// Returns true if the physical range `[base, base + len)` is entirely covered by usable
+    /// entries, which may be several adjacent ones. Returns false if any part of the range overlaps
+    /// with another type of memory or with a gap of the memory map.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # #[repr(C)]
+    /// # struct Map {
+    /// #     header: StivaleTagHeader,
+    /// #     len: u64,
+    /// #     entries: [StivaleMemoryMapEntry; 3],
+    /// # }
+    /// # let mut map = Map {
+    /// #     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    /// #     len: 3,
+    /// #     entries: [
+    /// #         StivaleMemoryMapEntry::new(0x0, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x1000, 0x1000, StivaleMemoryMapEntryType::Usable),
+    /// #         StivaleMemoryMapEntry::new(0x2000, 0x1000, StivaleMemoryMapEntryType::Reserved),
+    /// #     ],
+    /// # };
+    /// # let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 3) };
+    /// assert!(memory_map.is_range_usable(0x800, 0x1000));
+    /// assert!(!memory_map.is_range_usable(0x1800, 0x1000));
+    /// assert!(!memory_map.is_range_usable(0x3000, 0x1000));
+    /// ```
+    pub fn is_range_usable(&self, base: u64, len: u64) -> bool {
+        let end = match base.checked_add(len) {
+            Some(end) => end,
+            None => return false,
+        };
+
+        let mut covered = base;
+
+        for entry in self.entries_of_type(StivaleMemoryMapEntryType::Usable) {
+            if covered >= end || entry.base > covered {
+                break;
+            }
+
+            covered = covered.max(entry.end_address());
+        }
+
+        covered >= end
+    }
+
+    /// Copies the entries of the memory map into the provided storage, returning the amount of
+    /// entries that were copied. If the storage is too small, only the first entries are copied,
+    /// which can be detected by comparing the returned count with [StivaleMemoryMapTag::entries_len].
+    ///
+    /// This is useful before reclaiming the bootloader reclaimable memory, as the tag itself may
+    /// live in that memory.
+    pub fn copy_into(&self, dst: &mut [StivaleMemoryMapEntry]) -> usize {
+        let entries = self.as_slice();
+        let count = entries.len().min(dst.len());
+
+        dst[..count].copy_from_slice(&entries[..count]);
+        count
+    }
+
+    /// Writes an aligned table of the base, end, length and type of every entry of the memory
+    /// map to the provided writer, followed by the total amount of usable memory.
+    pub fn format_table(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(w, "{:<18} {:<18} {:>12} type", "base", "end", "length")?;
+
+        for entry in self.iter() {
+            // Copy the fields into locals so no references to the entry are formatted.
+            let base = entry.base;
+            let length = entry.length;
+            let entry_type = entry.entry_type();
+            let (size, unit) = size_with_unit(length);
+
+            writeln!(
+                w,
+                "{:#018x} {:#018x} {:>8} {} {}",
+                base,
+                base.wrapping_add(length),
+                size,
+                unit,
+                entry_type
+            )?;
+        }
+
+        let (size, unit) = size_with_unit(self.usable_memory());
+        writeln!(w, "usable memory: {} {}", size, unit)
+    }
+}
+
+impl Index<usize> for StivaleMemoryMapTag {
+    type Output = StivaleMemoryMapEntry;
+
+    /// Returns the memory map entry at the provided index.
+    ///
+    /// ## Panics
+    /// Panics if the index is out of bounds.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_slice()[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a StivaleMemoryMapTag {
+    type Item = &'a StivaleMemoryMapEntry;
+    type IntoIter = StivaleMemoryMapIter<'a>;
+
+    /// Returns an iterator over all the memory regions, see [StivaleMemoryMapTag::iter].
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// # use stivale_boot::v2::*;
+    /// # fn example(memory_map: &StivaleMemoryMapTag) {
+    /// for entry in memory_map {
+    ///     let end = entry.end_address();
+    /// }
+    /// # }
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
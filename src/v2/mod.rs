@@ -2,14 +2,94 @@
 //! modern version of the legacy stivale protocol which provides the kernel with most of the features
 //! one may need. The stivale2 protocol also supports 32-bit systems.
 
-use core::mem;
-
+use core::marker::PhantomData;
+
+mod acpi;
+mod boot_volume;
+mod capabilities;
+mod command_line;
+mod device_tree;
+mod edid;
+mod epoch;
+mod error;
+mod font;
+mod framebuffer;
 mod header;
+mod header_builder;
+mod ident;
+mod kernel_file;
+mod memory_map;
+mod module;
+#[cfg(feature = "print")]
+mod print;
+mod smbios;
+mod smp;
+mod struct_builder;
 mod tag;
+mod terminal;
+mod uart;
 mod utils;
 
+pub use acpi::*;
+pub use capabilities::*;
+pub use command_line::*;
+pub use device_tree::*;
+pub use edid::*;
+pub use epoch::*;
+pub use error::*;
+pub use font::*;
+pub use framebuffer::*;
 pub use header::*;
+pub use header_builder::*;
+pub use ident::*;
+pub use memory_map::*;
+#[cfg(feature = "print")]
+pub use print::*;
+pub use smbios::*;
+pub use smp::*;
+pub use struct_builder::*;
 pub use tag::*;
+pub use terminal::*;
+pub use uart::*;
+pub use utils::*;
+
+/// The maximum amount of tags that are walked in the tag chain before giving up. This guards
+/// against malformed chains where a corrupt or cyclic `next` pointer would otherwise loop forever.
+pub const MAX_TAG_COUNT: usize = 1024;
+
+/// Iterator over the headers of all the tags in the tag chain.
+///
+/// The iterator stops after [MAX_TAG_COUNT] tags so a corrupt or cyclic `next` pointer
+/// cannot make it loop forever.
+#[derive(Clone)]
+pub struct StivaleTagIter<'a> {
+    /// Pointer to the tag header that we are about to yield.
+    current: *const StivaleTagHeader,
+    /// The amount of tags that were already yielded.
+    count: usize,
+    phantom: PhantomData<&'a StivaleTagHeader>,
+}
+
+impl<'a> Iterator for StivaleTagIter<'a> {
+    type Item = &'a StivaleTagHeader;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_null() || self.count >= MAX_TAG_COUNT {
+            return None;
+        }
+
+        let tag = unsafe { &*self.current };
+        self.current = tag.next as *const StivaleTagHeader;
+        self.count += 1;
+
+        Some(tag)
+    }
+}
+
+// SAFETY: The iterator only reads the tags, which are immutable plain data handed off by the
+// bootloader, so it behaves like the `&'a StivaleTagHeader` it yields.
+unsafe impl Send for StivaleTagIter<'_> {}
+unsafe impl Sync for StivaleTagIter<'_> {}
 
 #[repr(C)]
 pub struct StivaleStruct {
@@ -18,7 +98,32 @@ pub struct StivaleStruct {
     tags: u64,
 }
 
+impl Default for StivaleStruct {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl StivaleStruct {
+    /// Returns a reference to the stivale2 struct at the provided pointer, such as the one passed
+    /// to the entry point of the kernel.
+    ///
+    /// ## Safety
+    /// `ptr` must point to a valid stivale2 struct which lives for `'static`, which means that
+    /// the bootloader reclaimable memory it and its tags live in must not have been reclaimed
+    /// and must never be reclaimed while the returned reference is in use.
+    pub unsafe fn from_ptr(ptr: *const StivaleStruct) -> &'static StivaleStruct {
+        &*ptr
+    }
+
+    /// Returns a reference to the stivale2 struct at the provided address.
+    ///
+    /// ## Safety
+    /// The same rules as for [StivaleStruct::from_ptr] apply.
+    pub unsafe fn from_addr(addr: u64) -> &'static StivaleStruct {
+        Self::from_ptr(addr as *const StivaleStruct)
+    }
+
     pub fn new() -> Self {
         Self {
             bootloader_brand: [0; 64],
@@ -43,181 +148,263 @@ impl StivaleStruct {
         self.bootloader_version[..version.len()].copy_from_slice(version.as_bytes());
     }
 
+    /// Returns the null terminated brand string of the bootloader.
     pub fn bootloader_brand(&self) -> &str {
         utils::string_from_slice(&self.bootloader_brand)
     }
 
+    /// Returns the null terminated version string of the bootloader.
     pub fn bootloader_version(&self) -> &str {
         utils::string_from_slice(&self.bootloader_version)
     }
 
-    pub fn get_tag(&self, identifier: u64) -> Option<u64> {
-        let mut current_tag = self.tags as *const StivaleTagHeader;
+    /// Shorthand for [StivaleStruct::bootloader_brand].
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::StivaleStruct;
+    /// let mut stivale_struct = StivaleStruct::new();
+    /// stivale_struct.set_bootloader_brand("Limine");
+    /// stivale_struct.set_bootloader_version("4.0");
+    ///
+    /// assert_eq!(stivale_struct.brand(), "Limine");
+    /// assert_eq!(stivale_struct.version(), "4.0");
+    /// ```
+    pub fn brand(&self) -> &str {
+        self.bootloader_brand()
+    }
+
+    /// Shorthand for [StivaleStruct::bootloader_version].
+    pub fn version(&self) -> &str {
+        self.bootloader_version()
+    }
+
+    /// Returns an iterator over the headers of all the tags provided by the bootloader.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// # fn example(stivale_struct: &stivale_boot::v2::StivaleStruct) {
+    /// for tag in stivale_struct.tags() {
+    ///     let address = tag as *const _ as u64;
+    ///     let identifier = tag.identifier;
+    /// }
+    /// # }
+    /// ```
+    pub fn tags(&self) -> StivaleTagIter<'_> {
+        StivaleTagIter {
+            current: self.tags as *const StivaleTagHeader,
+            count: 0,
+            phantom: PhantomData,
+        }
+    }
 
-        while !current_tag.is_null() {
-            let tag = unsafe { &*current_tag };
+    /// Returns the address of the first tag with the provided identifier, if present.
+    pub fn get_tag_addr(&self, identifier: u64) -> Option<u64> {
+        self.tags()
+            .find(|tag| tag.identifier == identifier)
+            .map(|tag| tag as *const StivaleTagHeader as u64)
+    }
 
-            if tag.identifier == identifier {
-                return Some(current_tag as u64);
+    /// Performs sanity checks on the stivale2 struct, which catch a corrupt hand-off early
+    /// instead of faulting while walking the tags. This checks that:
+    ///
+    /// * The bootloader brand and version strings are null terminated and valid UTF-8.
+    /// * The tag chain terminates within [MAX_TAG_COUNT] tags.
+    /// * No tag is located below the stivale2 struct.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut stivale_struct = StivaleStruct::new();
+    /// stivale_struct.set_bootloader_brand("Limine");
+    /// assert_eq!(stivale_struct.validate(), Ok(()));
+    ///
+    /// stivale_struct.set_bootloader_brand(&"A".repeat(64));
+    /// assert_eq!(stivale_struct.validate(), Err(StivaleError::InvalidString));
+    /// ```
+    pub fn validate(&self) -> Result<(), StivaleError> {
+        for string in [&self.bootloader_brand, &self.bootloader_version] {
+            let length = string
+                .iter()
+                .position(|&byte| byte == 0)
+                .ok_or(StivaleError::InvalidString)?;
+
+            core::str::from_utf8(&string[..length]).map_err(|_| StivaleError::InvalidString)?;
+        }
+
+        let struct_addr = self as *const Self as u64;
+        let mut current = self.tags;
+        let mut count = 0;
+
+        while current != 0 {
+            if current < struct_addr {
+                return Err(StivaleError::InvalidTagAddress(current));
             }
 
-            current_tag = tag.next as *const StivaleTagHeader;
+            if count == MAX_TAG_COUNT {
+                return Err(StivaleError::ChainTooLong);
+            }
+
+            current = unsafe { (*(current as *const StivaleTagHeader)).next };
+            count += 1;
         }
 
-        None
+        Ok(())
+    }
+
+    fn get_tag_ptr<T: StivaleTag + ?Sized>(&self) -> Option<*const T> {
+        self.get_tag_addr(T::IDENTIFIER)
+            .map(|addr| unsafe { T::from_header(addr as *const StivaleTagHeader) })
+    }
+
+    /// Returns the first tag of type `T` in the tag chain, if present.
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// # use stivale_boot::v2::*;
+    /// # fn example(stivale_struct: &StivaleStruct) {
+    /// let framebuffer = stivale_struct.get_tag::<StivaleFramebufferTag>();
+    /// # }
+    /// ```
+    pub fn get_tag<T: StivaleTag + ?Sized>(&self) -> Option<&T> {
+        self.get_tag_ptr::<T>().map(|ptr| unsafe { &*ptr })
     }
 
     pub fn command_line(&self) -> Option<&'static StivaleCommandLineTag> {
-        self.get_tag(0xe5e76a1b4597a781)
-            .map(|addr| unsafe { &*(addr as *const StivaleCommandLineTag) })
+        self.get_tag_ptr::<StivaleCommandLineTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn memory_map(&self) -> Option<&'static StivaleMemoryMapTag> {
-        self.get_tag(0x2187f79e8612de07).map(|addr| {
-            let ptr = addr as *mut u8;
-            unsafe {
-                let count = *(ptr.add(mem::size_of::<StivaleTagHeader>()) as *const u64);
-                let memory_map_ptr = StivaleMemoryMapTag::new_from_ptr_count(ptr as *mut (), count);
-                &*memory_map_ptr
-            }
-        })
+        self.get_tag_ptr::<StivaleMemoryMapTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn framebuffer(&self) -> Option<&'static StivaleFramebufferTag> {
-        self.get_tag(0x506461d2950408fa)
-            .map(|addr| unsafe { &*(addr as *const StivaleFramebufferTag) })
+        self.get_tag_ptr::<StivaleFramebufferTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn edid_info(&self) -> Option<&'static StivaleEdidInfoTag> {
-        self.get_tag(0x968609d7af96b845).map(|addr| {
-            let ptr = addr as *mut u8;
-            unsafe {
-                let count = *(ptr.add(mem::size_of::<StivaleTagHeader>()) as *const u64);
-                let edid_ptr = StivaleEdidInfoTag::new_from_ptr_count(ptr as *mut (), count);
-                &*edid_ptr
-            }
-        })
+        self.get_tag_ptr::<StivaleEdidInfoTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     #[allow(deprecated)]
     pub fn mtrr(&self) -> Option<&'static StivaleMtrrTag> {
-        self.get_tag(0x6bc1a78ebe871172)
-            .map(|addr| unsafe { &*(addr as *const StivaleMtrrTag) })
+        self.get_tag_ptr::<StivaleMtrrTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn terminal(&self) -> Option<&'static StivaleTerminalTag> {
-        self.get_tag(0xc2b3f4c3233b0974)
-            .map(|addr| unsafe { &*(addr as *const StivaleTerminalTag) })
+        self.get_tag_ptr::<StivaleTerminalTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn modules(&self) -> Option<&'static StivaleModuleTag> {
-        self.get_tag(0x4b6fe466aade04ce).map(|addr| {
-            let ptr = addr as *mut u8;
-            unsafe {
-                let count = *(ptr.add(mem::size_of::<StivaleTagHeader>()) as *const u64);
-                let module_ptr = StivaleModuleTag::new_from_ptr_count(ptr as *mut (), count);
-                &*module_ptr
-            }
-        })
+        self.get_tag_ptr::<StivaleModuleTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn rsdp(&self) -> Option<&'static StivaleRsdpTag> {
-        self.get_tag(0x9e1786930a375e78)
-            .map(|addr| unsafe { &*(addr as *const StivaleRsdpTag) })
+        self.get_tag_ptr::<StivaleRsdpTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn smbios(&self) -> Option<&'static StivaleSmbiosTag> {
-        self.get_tag(0x274bd246c62bf7d1)
-            .map(|addr| unsafe { &*(addr as *const StivaleSmbiosTag) })
+        self.get_tag_ptr::<StivaleSmbiosTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn epoch(&self) -> Option<&'static StivaleEpochTag> {
-        self.get_tag(0x566a7bed888e1407)
-            .map(|addr| unsafe { &*(addr as *const StivaleEpochTag) })
+        self.get_tag_ptr::<StivaleEpochTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn firmware(&self) -> Option<&'static StivaleFirmwareTag> {
-        self.get_tag(0x359d837855e3858c)
-            .map(|addr| unsafe { &*(addr as *const StivaleFirmwareTag) })
+        self.get_tag_ptr::<StivaleFirmwareTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn efi_system_table(&self) -> Option<&'static StivaleEfiSystemTableTag> {
-        self.get_tag(0x4bc5ec15845b558e)
-            .map(|addr| unsafe { &*(addr as *const StivaleEfiSystemTableTag) })
+        self.get_tag_ptr::<StivaleEfiSystemTableTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn kernel_file(&self) -> Option<&'static StivaleKernelFileTag> {
-        self.get_tag(0xe599d90c2975584a)
-            .map(|addr| unsafe { &*(addr as *const StivaleKernelFileTag) })
+        self.get_tag_ptr::<StivaleKernelFileTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn kernel_slide(&self) -> Option<&'static StivaleKernelSlideTag> {
-        self.get_tag(0xee80847d01506c57)
-            .map(|addr| unsafe { &*(addr as *const StivaleKernelSlideTag) })
+        self.get_tag_ptr::<StivaleKernelSlideTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn smp(&self) -> Option<&'static StivaleSmpTag> {
-        self.get_tag(0x34d1d96339647025).map(|addr| {
-            let ptr = addr as *mut u8;
-            unsafe {
-                // +32 calculated from the definition of the struct, offset to the cpu_count
-                let count = *(ptr.add(32) as *const u64);
-                let smp_ptr = StivaleSmpTag::new_from_ptr_count(ptr as *mut (), count);
-                &*smp_ptr
-            }
-        })
+        self.get_tag_ptr::<StivaleSmpTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn smp_mut(&mut self) -> Option<&'static mut StivaleSmpTag> {
-        self.get_tag(0x34d1d96339647025).map(|addr| {
-            let ptr = addr as *mut u8;
-            unsafe {
-                // +32 calculated from the definition of the struct, offset to the cpu_count
-                let count = *(ptr.add(32) as *const u64);
-                let smp_ptr = StivaleSmpTag::new_from_ptr_count(ptr as *mut (), count);
-                &mut *smp_ptr
-            }
-        })
+        self.get_tag_ptr::<StivaleSmpTag>()
+            .map(|ptr| unsafe { &mut *(ptr as *mut StivaleSmpTag) })
     }
 
     pub fn pxe_info(&self) -> Option<&'static StivalePxeInfoTag> {
-        self.get_tag(0x29d1e96239247032)
-            .map(|addr| unsafe { &*(addr as *const StivalePxeInfoTag) })
+        self.get_tag_ptr::<StivalePxeInfoTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn uart(&self) -> Option<&'static StivaleUartTag> {
-        self.get_tag(0xb813f9b8dbc78797)
-            .map(|addr| unsafe { &*(addr as *const StivaleUartTag) })
+        self.get_tag_ptr::<StivaleUartTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn dev_tree(&self) -> Option<&'static StivaleDeviceTreeTag> {
-        self.get_tag(0xabb29bd49a2833fa)
-            .map(|addr| unsafe { &*(addr as *const StivaleDeviceTreeTag) })
+        self.get_tag_ptr::<StivaleDeviceTreeTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn vmap(&self) -> Option<&'static StivaleVMapTag> {
-        self.get_tag(0xb0ed257db18cb58f)
-            .map(|addr| unsafe { &*(addr as *const StivaleVMapTag) })
+        self.get_tag_ptr::<StivaleVMapTag>()
+            .map(|ptr| unsafe { &*ptr })
+    }
+
+    pub fn boot_volume(&self) -> Option<&'static StivaleBootVolumeTag> {
+        self.get_tag_ptr::<StivaleBootVolumeTag>()
+            .map(|ptr| unsafe { &*ptr })
+    }
+
+    pub fn hhdm(&self) -> Option<&'static StivaleHhdmTag> {
+        self.get_tag_ptr::<StivaleHhdmTag>()
+            .map(|ptr| unsafe { &*ptr })
+    }
+
+    /// Returns the virtual address offset of the higher half direct map. The HHDM tag and the
+    /// legacy VMAP tag share their identifier and layout, so this works with both of them.
+    pub fn direct_map_offset(&self) -> Option<u64> {
+        self.hhdm().map(|hhdm| hhdm.addr())
+    }
+
+    /// Returns the virtual address of the provided physical address in the higher half direct
+    /// map, or `None` if the bootloader did not provide the HHDM or VMAP tag.
+    pub fn phys_to_virt(&self, phys: u64) -> Option<u64> {
+        self.hhdm().map(|hhdm| hhdm.phys_to_virt(phys))
     }
 
     pub fn kernel_file_v2(&self) -> Option<&'static StivaleKernelFileV2Tag> {
-        self.get_tag(0x37c13018a02c6ea2)
-            .map(|addr| unsafe { &*(addr as *const StivaleKernelFileV2Tag) })
+        self.get_tag_ptr::<StivaleKernelFileV2Tag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn pmrs(&self) -> Option<&'static StivalePmrsTag> {
-        self.get_tag(0x5df266a64047b6bd).map(|addr| {
-            let ptr = addr as *mut u8;
-            unsafe {
-                let count = *(ptr.add(mem::size_of::<StivaleTagHeader>()) as *const u64);
-                let pmrs_ptr = StivalePmrsTag::new_from_ptr_count(ptr as *mut (), count);
-                &*pmrs_ptr
-            }
-        })
+        self.get_tag_ptr::<StivalePmrsTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 
     pub fn kernel_base_addr(&self) -> Option<&'static StivaleKernelBaseAddressTag> {
-        self.get_tag(0x060d78874a2a8af0)
-            .map(|addr| unsafe { &*(addr as *const StivaleKernelBaseAddressTag) })
+        self.get_tag_ptr::<StivaleKernelBaseAddressTag>()
+            .map(|ptr| unsafe { &*ptr })
     }
 }
@@ -0,0 +1,46 @@
+//! This module contains the helpers of the stivale2 modules tag.
+
+use core::ops::Index;
+
+use super::tag::{StivaleModule, StivaleModuleIter, StivaleModuleTag};
+
+impl StivaleModule {
+    /// Returns the contents of this module as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.start as *const u8, self.size() as usize) }
+    }
+}
+
+impl StivaleModuleTag {
+    /// Returns the module at the provided index, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&StivaleModule> {
+        self.as_slice().get(index)
+    }
+
+    /// Returns the first module whose config string is exactly equal to the provided name. The
+    /// comparison is case-sensitive.
+    pub fn find(&self, name: &str) -> Option<&StivaleModule> {
+        self.iter().find(|module| module.as_str() == name)
+    }
+}
+
+impl Index<usize> for StivaleModuleTag {
+    type Output = StivaleModule;
+
+    /// Returns the module at the provided index.
+    ///
+    /// ## Panics
+    /// Panics if the index is out of bounds.
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_slice()[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a StivaleModuleTag {
+    type Item = &'a StivaleModule;
+    type IntoIter = StivaleModuleIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,63 @@
+//! This module contains the global terminal used by the [stivale_print!](crate::stivale_print)
+//! and [stivale_println!](crate::stivale_println) macros.
+
+use core::fmt::{self, Write};
+
+use spin::Mutex;
+
+use super::tag::StivaleTerminalTag;
+use super::terminal::StivaleTerminalWriter;
+
+static GLOBAL_TERMINAL: Mutex<Option<StivaleTerminalWriter>> = Mutex::new(None);
+
+/// Registers the provided terminal as the global terminal written to by the
+/// [stivale_print!](crate::stivale_print) and [stivale_println!](crate::stivale_println)
+/// macros. If the terminal does not provide a write function, the macros print nothing.
+///
+/// ## Example
+/// ```rust,no_run
+/// # fn example(stivale_struct: &'static stivale_boot::v2::StivaleStruct) {
+/// use stivale_boot::stivale_println;
+///
+/// if let Some(terminal) = stivale_struct.terminal() {
+///     stivale_boot::v2::set_global_terminal(terminal);
+/// }
+///
+/// stivale_println!("Booted by {}", stivale_struct.bootloader_brand());
+/// # }
+/// ```
+pub fn set_global_terminal(tag: &'static StivaleTerminalTag) {
+    *GLOBAL_TERMINAL.lock() = tag.writer();
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments<'_>) {
+    // The lock is held for the whole write since the terminal is **not** thread safe.
+    if let Some(writer) = GLOBAL_TERMINAL.lock().as_mut() {
+        let _ = writer.write_fmt(args);
+    }
+}
+
+/// Prints to the global terminal registered with [set_global_terminal].
+///
+/// The global terminal is locked while printing, so this must not be used from a context which
+/// may interrupt another print on the same CPU, such as an interrupt handler, as it would deadlock.
+#[macro_export]
+macro_rules! stivale_print {
+    ($($arg:tt)*) => {
+        $crate::v2::_print(format_args!($($arg)*))
+    };
+}
+
+/// Prints to the global terminal registered with [set_global_terminal], with a newline.
+///
+/// See [stivale_print!](crate::stivale_print) for the restrictions of printing.
+#[macro_export]
+macro_rules! stivale_println {
+    () => {
+        $crate::stivale_print!("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::v2::_print(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
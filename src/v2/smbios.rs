@@ -0,0 +1,178 @@
+//! This module contains helpers to read the SMBIOS tables located through the stivale2 SMBIOS tag.
+
+use super::tag::StivaleSmbiosTag;
+use super::utils;
+
+/// The type of the structure marking the end of the SMBIOS structure table.
+const END_OF_TABLE_TYPE: u8 = 127;
+
+/// SMBIOS entry point reported by the bootloader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmbiosEntry {
+    /// Address of the 32-bit (SMBIOS 2.x) entry point.
+    V2(u64),
+    /// Address of the 64-bit (SMBIOS 3.x) entry point.
+    V3(u64),
+}
+
+impl SmbiosEntry {
+    /// Returns the SMBIOS structure table described by this entry point, or `None` if the anchor
+    /// string of the entry point is wrong.
+    ///
+    /// ## Safety
+    /// The entry point and the table are read at their physical addresses, so the caller must
+    /// ensure that both are mapped there for the `'static` lifetime. This holds for an entry
+    /// point returned by [StivaleSmbiosTag::entry_point] as long as the firmware memory is
+    /// identity mapped.
+    pub unsafe fn table(&self) -> Option<&'static [u8]> {
+        let (address, length) = match *self {
+            SmbiosEntry::V2(entry) => {
+                let entry = entry as *const u8;
+
+                if core::slice::from_raw_parts(entry, 4) != b"_SM_" {
+                    return None;
+                }
+
+                let length = (entry.add(0x16) as *const u16).read_unaligned() as usize;
+                let address = (entry.add(0x18) as *const u32).read_unaligned() as u64;
+                (address, length)
+            }
+            SmbiosEntry::V3(entry) => {
+                let entry = entry as *const u8;
+
+                if core::slice::from_raw_parts(entry, 5) != b"_SM3_" {
+                    return None;
+                }
+
+                let length = (entry.add(0x0c) as *const u32).read_unaligned() as usize;
+                let address = (entry.add(0x10) as *const u64).read_unaligned();
+                (address, length)
+            }
+        };
+
+        if address == 0 {
+            return None;
+        }
+
+        Some(core::slice::from_raw_parts(address as *const u8, length))
+    }
+
+    /// Returns an iterator over the structures of the SMBIOS structure table described by this
+    /// entry point. See [SmbiosEntry::table] for more information.
+    ///
+    /// ## Safety
+    /// Same as [SmbiosEntry::table].
+    pub unsafe fn structures(&self) -> SmbiosStructureIter<'static> {
+        SmbiosStructureIter::new(self.table().unwrap_or(&[]))
+    }
+}
+
+/// A structure of the SMBIOS structure table.
+#[derive(Debug, Clone, Copy)]
+pub struct SmbiosStructure<'a> {
+    /// The type of the structure.
+    pub ty: u8,
+    /// The handle of the structure.
+    pub handle: u16,
+    /// The formatted area of the structure, including its 4 byte header.
+    pub formatted: &'a [u8],
+    /// The string set following the formatted area, without its final terminator.
+    string_set: &'a [u8],
+}
+
+impl<'a> SmbiosStructure<'a> {
+    /// Returns an iterator over the strings of the structure. Strings that are not valid UTF-8
+    /// are truncated to their longest valid prefix.
+    pub fn strings(&self) -> impl Iterator<Item = &'a str> {
+        self.string_set
+            .split(|&byte| byte == 0)
+            .filter(|string| !string.is_empty())
+            .map(utils::string_from_slice)
+    }
+
+    /// Returns the string with the provided index, as referenced from the formatted area. String
+    /// indices start at 1 and an index of 0 means that there is no string.
+    pub fn string(&self, index: u8) -> Option<&'a str> {
+        self.strings().nth((index as usize).checked_sub(1)?)
+    }
+}
+
+/// Iterator over the structures of an SMBIOS structure table, which stops at the end of table
+/// structure or at the first malformed structure.
+#[derive(Clone)]
+pub struct SmbiosStructureIter<'a> {
+    /// The remaining part of the table.
+    table: &'a [u8],
+}
+
+impl<'a> SmbiosStructureIter<'a> {
+    /// Creates a new iterator over the structures of the provided SMBIOS structure table.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::SmbiosStructureIter;
+    /// let table = [
+    ///     1, 5, 0x34, 0x12, 1, b'A', b'c', b'm', b'e', 0, b'X', 0, 0,
+    ///     127, 4, 0xff, 0xff, 0, 0,
+    /// ];
+    ///
+    /// let mut structures = SmbiosStructureIter::new(&table);
+    /// let system = structures.next().unwrap();
+    ///
+    /// assert_eq!((system.ty, system.handle), (1, 0x1234));
+    /// assert_eq!(system.string(system.formatted[4]), Some("Acme"));
+    /// assert_eq!(system.strings().count(), 2);
+    /// assert!(structures.next().is_none());
+    /// ```
+    pub fn new(table: &'a [u8]) -> Self {
+        Self { table }
+    }
+}
+
+impl<'a> Iterator for SmbiosStructureIter<'a> {
+    type Item = SmbiosStructure<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table;
+        let length = *table.get(1)? as usize;
+
+        if length < 4 || table[0] == END_OF_TABLE_TYPE {
+            self.table = &[];
+            return None;
+        }
+
+        // The string set is terminated by two consecutive null bytes.
+        let terminator = table
+            .get(length..)
+            .and_then(|strings| strings.windows(2).position(|pair| pair == [0, 0]));
+
+        let terminator = match terminator {
+            Some(terminator) => length + terminator,
+            None => {
+                self.table = &[];
+                return None;
+            }
+        };
+
+        self.table = &table[terminator + 2..];
+
+        Some(SmbiosStructure {
+            ty: table[0],
+            handle: u16::from_le_bytes([table[2], table[3]]),
+            formatted: &table[..length],
+            string_set: &table[length..terminator],
+        })
+    }
+}
+
+impl StivaleSmbiosTag {
+    /// Returns the SMBIOS entry point, preferring the 64-bit entry point if it is available.
+    /// Returns `None` if neither entry point is available.
+    pub fn entry_point(&self) -> Option<SmbiosEntry> {
+        match (self.smbios_entry_32, self.smbios_entry_64) {
+            (_, entry) if entry != 0 => Some(SmbiosEntry::V3(entry)),
+            (entry, _) if entry != 0 => Some(SmbiosEntry::V2(entry)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! This module contains the helpers of the stivale2 SMP tag.
+
+use core::cell::UnsafeCell;
+use core::ptr::addr_of_mut;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use super::header::StivaleSmpHeaderTagFlags;
+use super::tag::{StivaleSmpInfo, StivaleSmpTag};
+
+/// Summary of the SMP topology reported by the bootloader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmpSummary {
+    /// The total number of logical CPUs (including BSP).
+    pub total: u64,
+    /// LAPIC ID of the BSP (bootstrap processor).
+    pub bsp_lapic_id: u32,
+    /// True if X2APIC was successfully enabled by the bootloader.
+    pub x2apic: bool,
+}
+
+impl StivaleSmpInfo {
+    /// Returns the APIC ID of this CPU. If `x2apic` is true, which should be the value of
+    /// [StivaleSmpTag::x2apic_enabled], this is the full 32-bit X2APIC ID, otherwise this is the
+    /// 8-bit XAPIC ID.
+    pub fn apic_id(&self, x2apic: bool) -> u32 {
+        if x2apic {
+            self.lapic_id
+        } else {
+            self.lapic_id & 0xff
+        }
+    }
+}
+
+impl StivaleSmpTag {
+    /// Returns a summary of the SMP topology reported by the bootloader.
+    pub fn summary(&self) -> SmpSummary {
+        SmpSummary {
+            total: self.cpu_count(),
+            bsp_lapic_id: self.bsp_lapic_id,
+            x2apic: self.x2apic_enabled(),
+        }
+    }
+
+    /// Returns the SMP flags passed by the bootloader.
+    pub fn flags(&self) -> StivaleSmpHeaderTagFlags {
+        self.flags
+    }
+
+    /// Returns true if X2APIC was requested and successfully enabled by the bootloader, in which
+    /// case the LAPIC IDs are X2APIC IDs.
+    pub fn x2apic_enabled(&self) -> bool {
+        self.flags().contains(StivaleSmpHeaderTagFlags::X2APIC)
+    }
+
+    /// Starts the application processor described by `info`, by writing the provided stack to
+    /// [StivaleSmpInfo::target_stack] and then atomically storing the entry point to
+    /// [StivaleSmpInfo::goto_address] with release ordering. Once the store is performed, the
+    /// AP loads the stack and jumps to the entry point with a pointer to `info` as its argument.
+    ///
+    /// ## Panics
+    /// Panics if `info` does not point to an entry of the SMP info array of this tag.
+    ///
+    /// ## Safety
+    /// - This must only be called once per AP, and `info` must not describe the BSP.
+    /// - `info` must be valid for writes, as the bootloader memory holding the tag is written to.
+    /// - `stack_top` must be the top of a valid, 16-byte aligned stack of at least 256 bytes that
+    ///   is exclusively used by the AP.
+    /// - The stack and the goto address of `info` must not be written by anything else while the
+    ///   AP may still be polling it.
+    pub unsafe fn start_ap(
+        &self,
+        info: *mut StivaleSmpInfo,
+        stack_top: u64,
+        entry: extern "C" fn(&'static StivaleSmpInfo) -> !,
+    ) {
+        assert!(
+            self.as_slice()
+                .iter()
+                .any(|entry| core::ptr::eq(entry, info)),
+            "info must be an entry of this tag"
+        );
+
+        debug_assert_ne!(
+            (*info).apic_id(self.x2apic_enabled()),
+            self.bsp_lapic_id,
+            "the BSP cannot be started"
+        );
+
+        addr_of_mut!((*info).target_stack).write(stack_top);
+        AtomicU64::from_ptr(addr_of_mut!((*info).goto_address))
+            .store(entry as usize as u64, Ordering::Release);
+    }
+
+    /// Returns the CPU with the provided LAPIC ID, which is an X2APIC ID if X2APIC is enabled.
+    pub fn cpu_by_lapic_id(&self, id: u32) -> Option<&StivaleSmpInfo> {
+        let x2apic = self.x2apic_enabled();
+
+        self.as_slice()
+            .iter()
+            .find(|info| info.apic_id(x2apic) == id)
+    }
+
+    /// Returns the CPU with the provided ACPI processor UID, as specified by the MADT.
+    pub fn cpu_by_acpi_uid(&self, uid: u32) -> Option<&StivaleSmpInfo> {
+        self.as_slice()
+            .iter()
+            .find(|info| info.acpi_processor_uid == uid)
+    }
+
+    /// Returns the entry describing the BSP (bootstrap processor).
+    pub fn bsp(&self) -> Option<&StivaleSmpInfo> {
+        self.cpu_by_lapic_id(self.bsp_lapic_id)
+    }
+
+    /// Returns an iterator over the application processors, which excludes the BSP. The LAPIC IDs
+    /// are compared the same way as by [StivaleSmpTag::bsp], so the BSP is never an AP.
+    pub fn aps(&self) -> impl Iterator<Item = &StivaleSmpInfo> {
+        let bsp_lapic_id = self.bsp_lapic_id;
+        let x2apic = self.x2apic_enabled();
+
+        self.as_slice()
+            .iter()
+            .filter(move |info| info.apic_id(x2apic) != bsp_lapic_id)
+    }
+}
+
+/// Pool of `N` stacks of `SIZE` bytes each, meant to be declared as a static and to provide the
+/// stacks of the APs passed to [StivaleSmpTag::start_ap]. `SIZE` must be a multiple of 16 and at
+/// least 256 bytes, as required by the stivale2 specification, which is checked at compile time.
+///
+/// ## Example
+/// ```rust
+/// use stivale_boot::v2::SmpStacks;
+///
+/// static AP_STACKS: SmpStacks<4, 0x4000> = SmpStacks::new();
+///
+/// assert_eq!(AP_STACKS.stack_top(0) % 16, 0);
+/// assert_eq!(AP_STACKS.stack_top(1) - AP_STACKS.stack_top(0), 0x4000);
+/// ```
+#[repr(C, align(16))]
+pub struct SmpStacks<const N: usize, const SIZE: usize> {
+    stacks: UnsafeCell<[[u8; SIZE]; N]>,
+}
+
+// SAFETY: The stacks are never accessed through the pool, it only hands out their addresses.
+unsafe impl<const N: usize, const SIZE: usize> Sync for SmpStacks<N, SIZE> {}
+
+impl<const N: usize, const SIZE: usize> SmpStacks<N, SIZE> {
+    const VALID_SIZE: () = assert!(
+        SIZE.is_multiple_of(16) && SIZE >= 256,
+        "the size of the stacks must be a multiple of 16 and at least 256 bytes"
+    );
+
+    /// Creates a new pool of zeroed stacks.
+    #[allow(clippy::let_unit_value)]
+    pub const fn new() -> Self {
+        let _ = Self::VALID_SIZE;
+
+        Self {
+            stacks: UnsafeCell::new([[0; SIZE]; N]),
+        }
+    }
+
+    /// Returns the top of the stack with the provided index, which is 16-byte aligned as the
+    /// stacks grow downwards.
+    ///
+    /// ## Panics
+    /// Panics if `cpu` is not smaller than `N`.
+    pub fn stack_top(&self, cpu: usize) -> u64 {
+        assert!(cpu < N, "stack index out of bounds");
+
+        self.stacks.get() as u64 + ((cpu + 1) * SIZE) as u64
+    }
+}
+
+impl<const N: usize, const SIZE: usize> Default for SmpStacks<N, SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,219 @@
+//! This module contains a builder which writes a stivale2 struct and its tag chain into a caller
+//! provided buffer, which is useful for second stage loaders forwarding the tags to a payload.
+
+use super::error::StivaleError;
+use super::tag::*;
+use super::StivaleStruct;
+
+/// The alignment of the stivale2 struct and of every tag written by the builder.
+const TAG_ALIGNMENT: usize = 8;
+
+/// Returns the size of the provided tag in bytes, including the entries of variable length
+/// tags, or `None` if the identifier of the tag is unknown.
+///
+/// ## Safety
+/// `header` must point to a valid tag.
+#[allow(deprecated)]
+unsafe fn tag_size(header: *const StivaleTagHeader) -> Option<usize> {
+    macro_rules! tag_size {
+        ($($name:ident),* $(,)?) => {
+            $(if (*header).identifier == $name::IDENTIFIER {
+                return Some(core::mem::size_of_val(&*$name::from_header(header)));
+            })*
+        };
+    }
+
+    tag_size!(
+        StivaleFramebufferTag,
+        StivaleTerminalTag,
+        StivaleRsdpTag,
+        StivaleEpochTag,
+        StivaleFirmwareTag,
+        StivaleEfiSystemTableTag,
+        StivaleKernelFileTag,
+        StivaleKernelSlideTag,
+        StivaleCommandLineTag,
+        StivaleMtrrTag,
+        StivaleSmbiosTag,
+        StivalePxeInfoTag,
+        StivaleUartTag,
+        StivaleDeviceTreeTag,
+        StivaleHhdmTag,
+        StivaleBootVolumeTag,
+        StivaleKernelFileV2Tag,
+        StivaleKernelBaseAddressTag,
+        StivaleMemoryMapTag,
+        StivaleEdidInfoTag,
+        StivaleModuleTag,
+        StivaleSmpTag,
+        StivalePmrsTag,
+    );
+
+    None
+}
+
+/// Builder writing a fresh stivale2 struct followed by a contiguous tag chain into a caller
+/// provided buffer. Tags can be copied from an existing stivale2 struct or appended, and are
+/// linked together in the order they are added.
+///
+/// The tags are copied as is, so the memory that they point to, such as the command line or the
+/// modules, is shared with the original tags.
+///
+/// ## Example
+/// ```rust
+/// # use stivale_boot::v2::*;
+/// let mut buffer = [0u8; 512];
+/// let mut builder = StivaleStructBuilder::new(&mut buffer).unwrap();
+/// builder.set_bootloader_brand("chainloader");
+///
+/// let epoch = StivaleEpochTag {
+///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_EPOCH_ID, next: 0 },
+///     epoch: 1700000000,
+/// };
+/// builder.append(&epoch).unwrap();
+///
+/// let (offset, _length) = builder.finish();
+/// let stivale_struct = unsafe { &*(buffer[offset..].as_ptr() as *const StivaleStruct) };
+///
+/// assert_eq!(stivale_struct.bootloader_brand(), "chainloader");
+/// assert_eq!(stivale_struct.epoch().unwrap().epoch, 1700000000);
+///
+/// // Forward every tag of the stivale2 struct to another one.
+/// let mut forwarded = [0u8; 512];
+/// let mut builder = StivaleStructBuilder::new(&mut forwarded).unwrap();
+///
+/// for tag in stivale_struct.tags() {
+///     unsafe { builder.copy_tag(tag) }.unwrap();
+/// }
+///
+/// let (offset, _length) = builder.finish();
+/// let stivale_struct = unsafe { &*(forwarded[offset..].as_ptr() as *const StivaleStruct) };
+/// assert_eq!(stivale_struct.epoch().unwrap().epoch, 1700000000);
+/// ```
+pub struct StivaleStructBuilder<'a> {
+    buffer: &'a mut [u8],
+    /// Offset of the stivale2 struct in the buffer.
+    start: usize,
+    /// Offset of the end of the written data in the buffer.
+    end: usize,
+    /// Offset of the last tag that was written, or `None` if no tag was written yet.
+    last_tag: Option<usize>,
+}
+
+impl<'a> StivaleStructBuilder<'a> {
+    /// Creates a new builder writing into the provided buffer, starting with an empty stivale2
+    /// struct at the first suitably aligned offset.
+    ///
+    /// Returns [StivaleError::BufferTooSmall] if the stivale2 struct does not fit in the buffer.
+    pub fn new(buffer: &'a mut [u8]) -> Result<Self, StivaleError> {
+        let mut builder = Self {
+            buffer,
+            start: 0,
+            end: 0,
+            last_tag: None,
+        };
+
+        let head = StivaleStruct::new();
+        builder.start = unsafe { builder.write(&head) }?;
+
+        Ok(builder)
+    }
+
+    /// Returns the stivale2 struct being written.
+    fn head_mut(&mut self) -> &mut StivaleStruct {
+        unsafe { &mut *(self.buffer.as_mut_ptr().add(self.start) as *mut StivaleStruct) }
+    }
+
+    /// Sets the bootloader brand of the stivale2 struct being written.
+    pub fn set_bootloader_brand(&mut self, brand: &str) {
+        self.head_mut().set_bootloader_brand(brand);
+    }
+
+    /// Sets the bootloader version of the stivale2 struct being written.
+    pub fn set_bootloader_version(&mut self, version: &str) {
+        self.head_mut().set_bootloader_version(version);
+    }
+
+    /// Copies the provided value to the next suitably aligned offset of the buffer, returning
+    /// that offset. The value is copied as raw memory, so its padding bytes are copied as well.
+    ///
+    /// ## Safety
+    /// `value` must be valid for reads of `size_of_val(value)` bytes.
+    unsafe fn write<T: ?Sized>(&mut self, value: &T) -> Result<usize, StivaleError> {
+        let size = core::mem::size_of_val(value);
+        let base = self.buffer.as_ptr() as usize;
+        let offset = (base + self.end).next_multiple_of(TAG_ALIGNMENT) - base;
+        let end = offset
+            .checked_add(size)
+            .filter(|&end| end <= self.buffer.len())
+            .ok_or(StivaleError::BufferTooSmall)?;
+
+        core::ptr::copy_nonoverlapping(
+            value as *const T as *const u8,
+            self.buffer.as_mut_ptr().add(offset),
+            size,
+        );
+        self.end = end;
+
+        Ok(offset)
+    }
+
+    /// Appends the provided tag to the tag chain. The `next` pointer of the copy is cleared and
+    /// the previously written tag, or the stivale2 struct if this is the first tag, is linked
+    /// to it.
+    ///
+    /// Returns [StivaleError::BufferTooSmall] if the tag does not fit in the buffer.
+    pub fn append<T: StivaleTag + ?Sized>(&mut self, tag: &T) -> Result<(), StivaleError> {
+        let offset = unsafe { self.write(tag) }?;
+        self.link(offset);
+
+        Ok(())
+    }
+
+    /// Copies the tag with the provided header, such as one yielded by
+    /// [StivaleStruct::tags], to the tag chain. The size of variable length tags is computed
+    /// from their entry count.
+    ///
+    /// Returns [StivaleError::UnknownTag] if the identifier of the tag is unknown, as its size
+    /// cannot be known, and [StivaleError::BufferTooSmall] if the tag does not fit in the buffer.
+    ///
+    /// ## Safety
+    /// `header` must be the header of a valid tag.
+    pub unsafe fn copy_tag(&mut self, header: &StivaleTagHeader) -> Result<(), StivaleError> {
+        let size = tag_size(header).ok_or(StivaleError::UnknownTag(header.identifier))?;
+        let tag =
+            core::ptr::slice_from_raw_parts(header as *const StivaleTagHeader as *const u8, size);
+
+        let offset = self.write(&*tag)?;
+        self.link(offset);
+
+        Ok(())
+    }
+
+    /// Clears the `next` pointer of the tag written at the provided offset and links it to the
+    /// end of the tag chain.
+    fn link(&mut self, offset: usize) {
+        let address = self.buffer.as_ptr() as u64 + offset as u64;
+
+        unsafe {
+            let header = self.buffer.as_mut_ptr().add(offset) as *mut StivaleTagHeader;
+            (*header).next = 0;
+
+            match self.last_tag {
+                Some(last) => {
+                    let last = self.buffer.as_mut_ptr().add(last) as *mut StivaleTagHeader;
+                    (*last).next = address;
+                }
+                None => self.head_mut().tags = address,
+            }
+        }
+
+        self.last_tag = Some(offset);
+    }
+
+    /// Finishes the stivale2 struct, returning the offset of the stivale2 struct in the buffer
+    /// and the amount of bytes written from that offset.
+    pub fn finish(self) -> (usize, usize) {
+        (self.start, self.end - self.start)
+    }
+}
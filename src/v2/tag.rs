@@ -1,13 +1,146 @@
 use core::marker::PhantomData;
+use core::net::Ipv4Addr;
 
+use super::error::StivaleError;
 use super::header::StivaleSmpHeaderTagFlags;
+use super::ident::*;
+use super::utils;
+use super::utils::Guid;
 
 #[repr(C)]
+#[derive(Debug, Clone, Copy)]
 pub struct StivaleTagHeader {
     pub identifier: u64,
     pub next: u64,
 }
 
+/// Trait implemented by every stivale2 struct tag, associating the tag with its identifier
+/// so it can be looked up generically through [crate::v2::StivaleStruct::get_tag].
+///
+/// ## Safety
+/// The implementing type must have the exact memory layout of the tag described by
+/// [StivaleTag::IDENTIFIER] in the stivale2 specification, starting with a [StivaleTagHeader].
+pub unsafe trait StivaleTag {
+    /// The identifier of this tag as specified by the stivale2 specification.
+    const IDENTIFIER: u64;
+
+    /// Returns a pointer to the tag from a pointer to its header. For variable length tags this
+    /// is responsible for reading the entry count and building the fat pointer.
+    ///
+    /// # Safety
+    /// `header` must point to a properly initialized tag with the identifier [StivaleTag::IDENTIFIER].
+    unsafe fn from_header(header: *const StivaleTagHeader) -> *const Self;
+
+    /// Returns a copy of the header of this tag.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// fn describe<T: StivaleTag + ?Sized>(tag: &T) -> (u64, bool) {
+    ///     (tag.identifier(), tag.header().next == 0)
+    /// }
+    ///
+    /// let epoch = StivaleEpochTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_EPOCH_ID, next: 0 },
+    ///     epoch: 0,
+    /// };
+    /// assert_eq!(describe(&epoch), (STIVALE2_STRUCT_TAG_EPOCH_ID, true));
+    /// ```
+    fn header(&self) -> StivaleTagHeader {
+        // SAFETY: Implementors are required to start with a tag header.
+        unsafe { (self as *const Self).cast::<StivaleTagHeader>().read() }
+    }
+
+    /// Returns the identifier stored in the header of this tag.
+    fn identifier(&self) -> u64 {
+        self.header().identifier
+    }
+}
+
+macro_rules! impl_stivale_tag {
+    ($($name:ident: $id:expr),* $(,)?) => {
+        $(#[allow(deprecated)] unsafe impl StivaleTag for $name {
+            const IDENTIFIER: u64 = $id;
+
+            unsafe fn from_header(header: *const StivaleTagHeader) -> *const Self {
+                header as *const Self
+            }
+        })*
+    };
+}
+
+/// Implements [StivaleTag] for a variable length tag whose entry count is stored `$count_offset`
+/// bytes after the start of the tag.
+macro_rules! impl_stivale_slice_tag {
+    ($($name:ident: $id:expr, $count_offset:expr),* $(,)?) => {
+        $(unsafe impl StivaleTag for $name {
+            const IDENTIFIER: u64 = $id;
+
+            unsafe fn from_header(header: *const StivaleTagHeader) -> *const Self {
+                let ptr = header as *mut u8;
+                let count = *(ptr.add($count_offset) as *const u64);
+                $name::new_from_ptr_count(ptr as *mut (), count)
+            }
+        })*
+    };
+}
+
+impl_stivale_tag!(
+    StivaleFramebufferTag: STIVALE2_STRUCT_TAG_FRAMEBUFFER_ID,
+    StivaleTerminalTag: STIVALE2_STRUCT_TAG_TERMINAL_ID,
+    StivaleRsdpTag: STIVALE2_STRUCT_TAG_RSDP_ID,
+    StivaleEpochTag: STIVALE2_STRUCT_TAG_EPOCH_ID,
+    StivaleFirmwareTag: STIVALE2_STRUCT_TAG_FIRMWARE_ID,
+    StivaleEfiSystemTableTag: STIVALE2_STRUCT_TAG_EFI_SYSTEM_TABLE_ID,
+    StivaleKernelFileTag: STIVALE2_STRUCT_TAG_KERNEL_FILE_ID,
+    StivaleKernelSlideTag: STIVALE2_STRUCT_TAG_KERNEL_SLIDE_ID,
+    StivaleCommandLineTag: STIVALE2_STRUCT_TAG_CMDLINE_ID,
+    StivaleMtrrTag: STIVALE2_STRUCT_TAG_FB_MTRR_ID,
+    StivaleSmbiosTag: STIVALE2_STRUCT_TAG_SMBIOS_ID,
+    StivalePxeInfoTag: STIVALE2_STRUCT_TAG_PXE_SERVER_INFO_ID,
+    StivaleUartTag: STIVALE2_STRUCT_TAG_MMIO32_UART_ID,
+    StivaleDeviceTreeTag: STIVALE2_STRUCT_TAG_DTB_ID,
+    StivaleVMapTag: STIVALE2_STRUCT_TAG_VMAP_ID,
+    StivaleHhdmTag: STIVALE2_STRUCT_TAG_HHDM_ID,
+    StivaleBootVolumeTag: STIVALE2_STRUCT_TAG_BOOT_VOLUME_ID,
+    StivaleKernelFileV2Tag: STIVALE2_STRUCT_TAG_KERNEL_FILE_V2_ID,
+    StivaleKernelBaseAddressTag: STIVALE2_STRUCT_TAG_KERNEL_BASE_ADDRESS_ID,
+);
+
+impl_stivale_slice_tag!(
+    StivaleMemoryMapTag: STIVALE2_STRUCT_TAG_MEMMAP_ID, core::mem::size_of::<StivaleTagHeader>(),
+    StivaleEdidInfoTag: STIVALE2_STRUCT_TAG_EDID_ID, core::mem::size_of::<StivaleTagHeader>(),
+    StivaleModuleTag: STIVALE2_STRUCT_TAG_MODULES_ID, core::mem::size_of::<StivaleTagHeader>(),
+    // +32 calculated from the definition of the struct, offset to the cpu_count
+    StivaleSmpTag: STIVALE2_STRUCT_TAG_SMP_ID, 32,
+    StivalePmrsTag: STIVALE2_STRUCT_TAG_PMRS_ID, core::mem::size_of::<StivaleTagHeader>(),
+);
+
+// The tags only contain plain data describing memory owned by the kernel after the hand-off, so
+// they are `Send` and `Sync` and references to them can be shared with the APs after SMP
+// bring-up. This ensures that no field added later silently removes these auto traits.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+    assert_send_sync::<StivaleMemoryMapTag>();
+    assert_send_sync::<StivaleMemoryMapIter<'static>>();
+    assert_send_sync::<StivaleModuleTag>();
+    assert_send_sync::<StivaleModuleIter<'static>>();
+    assert_send_sync::<StivaleSmpTag>();
+    assert_send_sync::<StivaleSmpInfo>();
+    assert_send_sync::<StivaleFramebufferTag>();
+    assert_send_sync::<StivaleTerminalTag>();
+};
+
+/// The memory model of the framebuffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StivaleFramebufferMemoryModel {
+    /// RGB memory model, represented by a value of `1`.
+    Rgb,
+    /// Any other, undefined, memory model.
+    Unknown(u8),
+}
+
 /// If the framebuffer tag was requested through the framebuffer tag header and its supported by the stivale
 /// bootloader, this tag is returned to the kernel. This tag provides an interface to the framebuffer.
 #[repr(C)]
@@ -42,11 +175,65 @@ pub struct StivaleFramebufferTag {
 }
 
 impl StivaleFramebufferTag {
-    /// Returns the size of the framebuffer.
+    /// Returns the memory model of the framebuffer.
+    pub fn memory_model(&self) -> StivaleFramebufferMemoryModel {
+        match self.memory_model {
+            1 => StivaleFramebufferMemoryModel::Rgb,
+            model => StivaleFramebufferMemoryModel::Unknown(model),
+        }
+    }
+
+    /// Returns the size of the framebuffer in bytes. The pitch already accounts for the
+    /// bytes-per-pixel so the size is `pitch * height`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::StivaleFramebufferTag;
+    /// let mut tag: StivaleFramebufferTag = unsafe { core::mem::zeroed() };
+    /// tag.framebuffer_pitch = 4096;
+    /// tag.framebuffer_height = 768;
+    /// tag.framebuffer_bpp = 32;
+    ///
+    /// assert_eq!(tag.size(), 3145728);
+    /// ```
     pub fn size(&self) -> usize {
-        self.framebuffer_pitch as usize
-            * self.framebuffer_height as usize
-            * (self.framebuffer_bpp as usize / 8)
+        self.framebuffer_pitch as usize * self.framebuffer_height as usize
+    }
+
+    /// Returns the size of the framebuffer in bytes, or `None` if it overflows a `usize`.
+    pub fn size_checked(&self) -> Option<usize> {
+        (self.framebuffer_pitch as usize).checked_mul(self.framebuffer_height as usize)
+    }
+
+    /// Returns the framebuffer memory as a rust slice of `pitch * height` bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.framebuffer_addr as *const u8, self.size()) }
+    }
+
+    /// Returns the framebuffer memory as a mutable rust slice of `pitch * height` bytes.
+    ///
+    /// ## Safety
+    /// The returned slice aliases the framebuffer memory, which is shared by every caller of this
+    /// function and of the other framebuffer helpers. The caller must be the single owner of the
+    /// framebuffer for as long as the returned slice is alive.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_mut_slice(&self) -> &mut [u8] {
+        core::slice::from_raw_parts_mut(self.framebuffer_addr as *mut u8, self.size())
+    }
+}
+
+bitflags::bitflags! {
+    /// Bitfield representing the terminal tag flags passed by the bootloader.
+    pub struct StivaleTerminalFlags: u32 {
+        /// The columns and rows of the terminal are provided.
+        const COLS_AND_ROWS = 1 << 0;
+        /// The maximum length of a single write is provided. If unset, a maximum length of
+        /// 1024 is assumed.
+        const MAX_LENGTH = 1 << 1;
+        /// The callback was requested and is supported by the bootloader.
+        const CALLBACK = 1 << 2;
+        /// Context control is available.
+        const CONTEXT_CONTROL = 1 << 3;
     }
 }
 
@@ -55,6 +242,7 @@ impl StivaleFramebufferTag {
 #[repr(C)]
 pub struct StivaleTerminalTag {
     pub header: StivaleTagHeader,
+    /// Flags telling which terminal features are available, see [StivaleTerminalFlags].
     pub flags: u32,
     /// The amount of columns in the stivale terminal setup by the bootloader.
     pub cols: u16,
@@ -63,32 +251,69 @@ pub struct StivaleTerminalTag {
     /// The virtual address of the `term_write` function, which is used to write to the stivale terminal. For
     /// a more safer way use the [StivaleTerminalTag::term_write]
     pub term_write_addr: u64,
+    /// The maximum length of a single write to the terminal. Only valid if
+    /// [StivaleTerminalFlags::MAX_LENGTH] is set.
+    pub max_length: u64,
 }
 
 impl StivaleTerminalTag {
+    /// Returns the `term_write` function transmuted from [StivaleTerminalTag::term_write_addr],
+    /// or `None` if the address is zero.
+    pub(crate) fn term_func(&self) -> Option<extern "C" fn(*const i8, u64)> {
+        if self.term_write_addr == 0 {
+            return None;
+        }
+
+        let __fn_ptr = self.term_write_addr as *const ();
+        Some(unsafe { core::mem::transmute::<*const (), extern "C" fn(*const i8, u64)>(__fn_ptr) })
+    }
+
     /// Returns the terminal write function provided by the terminal stivale tag. This function
     /// returns the transmuted function for you to simplify the process of passing the string as a raw pointer
-    /// and passing the string length.
+    /// and passing the string length. Returns `None` if the bootloader did not provide a terminal
+    /// write function.
+    ///
+    /// Strings longer than [StivaleTerminalTag::max_length] are split into several writes, as a
+    /// single longer write is undefined behavior.
     ///
     /// ## Example
     /// ```rust,no_run
+    /// # fn example(stivale_struct: &stivale_boot::v2::StivaleStruct) {
     /// let terminal_tag = stivale_struct.terminal().expect("Terminal tag was provided by the stivale2 bootloader");
-    /// let term_write = terminal_tag.term_write();
+    /// let term_write = terminal_tag.term_write().expect("Terminal write function was provided");
     ///
     /// term_write("Hello, Stivale!");
     /// term_write("Hello, Rust!")
+    /// # }
+    /// ```
+    ///
+    /// ```rust
+    /// # use core::sync::atomic::{AtomicUsize, Ordering};
+    /// # use stivale_boot::v2::*;
+    /// static CALLS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// extern "C" fn write(_: *const i8, len: u64) {
+    ///     assert!(len <= 4096);
+    ///     CALLS.fetch_add(1, Ordering::Relaxed);
+    /// }
+    ///
+    /// let mut tag: StivaleTerminalTag = unsafe { core::mem::zeroed() };
+    /// tag.flags = StivaleTerminalFlags::MAX_LENGTH.bits();
+    /// tag.max_length = 4096;
+    /// tag.term_write_addr = write as usize as u64;
+    ///
+    /// let text = [b'a'; 5000];
+    /// let term_write = tag.term_write().unwrap();
+    /// term_write(core::str::from_utf8(&text).unwrap());
+    /// assert_eq!(CALLS.load(Ordering::Relaxed), 2);
     /// ```
     ///
     /// ## Safety
     /// This function is **not** thread safe.
-    pub fn term_write(&self) -> impl Fn(&str) {
-        let __fn_ptr = self.term_write_addr as *const ();
-        let __term_func =
-            unsafe { core::mem::transmute::<*const (), extern "C" fn(*const i8, u64)>(__fn_ptr) };
+    pub fn term_write(&self) -> Option<impl Fn(&str)> {
+        let writer = self.writer()?;
 
-        move |txt| {
-            __term_func(txt.as_ptr() as *const i8, txt.len() as u64);
-        }
+        Some(move |txt: &str| writer.write_bytes(txt.as_bytes()))
     }
 }
 
@@ -108,7 +333,7 @@ pub struct StivaleRsdpTag {
 /// base and length. Usable and bootloader reclaimable entries are **guaranteed** not to overlap with
 /// any other entry.
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum StivaleMemoryMapEntryType {
     /// Usable memory.
     Usable = 1,
@@ -142,10 +367,34 @@ pub struct StivaleMemoryMapEntry {
 }
 
 impl StivaleMemoryMapEntry {
+    /// Creates a new memory map entry. This is useful when making a stivale2 bootloader.
+    pub const fn new(base: u64, length: u64, entry_type: StivaleMemoryMapEntryType) -> Self {
+        Self {
+            base,
+            length,
+            entry_type,
+            _padding: 0,
+        }
+    }
+
     /// Returns the end address of this memory region.
+    ///
+    /// ## Panics
+    /// Panics in debug builds if the end address overflows a `u64`, see
+    /// [StivaleMemoryMapEntry::end_address_checked].
     #[inline]
     pub fn end_address(&self) -> u64 {
-        self.base + self.length
+        debug_assert!(
+            self.end_address_checked().is_some(),
+            "memory map entry end address overflows"
+        );
+        self.base.wrapping_add(self.length)
+    }
+
+    /// Returns the end address of this memory region, or `None` if it overflows a `u64`.
+    #[inline]
+    pub fn end_address_checked(&self) -> Option<u64> {
+        self.base.checked_add(self.length)
     }
 
     /// Returns the entry type of this memory region. External function is required
@@ -156,6 +405,26 @@ impl StivaleMemoryMapEntry {
     }
 }
 
+// The comparisons and the hash only take the base, the length and the type of the entries into
+// account, as the padding is not guaranteed to be zeroed by the bootloader.
+impl PartialEq for StivaleMemoryMapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+            && self.length == other.length
+            && self.entry_type == other.entry_type
+    }
+}
+
+impl Eq for StivaleMemoryMapEntry {}
+
+impl core::hash::Hash for StivaleMemoryMapEntry {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.base.hash(state);
+        self.length.hash(state);
+        self.entry_type.hash(state);
+    }
+}
+
 #[repr(C)]
 pub struct StivaleMemoryMapTag {
     pub header: StivaleTagHeader,
@@ -171,6 +440,30 @@ impl StivaleMemoryMapTag {
         unsafe { core::slice::from_raw_parts(self.entry_array.as_ptr(), self.entries_len as usize) }
     }
 
+    /// Returns the memory map entries as a rust slice, or an error if the entries pointer is
+    /// null or the length of the memory map is implausibly large.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// #[repr(C)]
+    /// struct Map {
+    ///     header: StivaleTagHeader,
+    ///     len: u64,
+    /// }
+    ///
+    /// let mut map = Map {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MEMMAP_ID, next: 0 },
+    ///     len: 1 << 40,
+    /// };
+    /// let memory_map = unsafe { &*StivaleMemoryMapTag::new_from_ptr_count(&mut map as *mut Map as *mut (), 0) };
+    ///
+    /// assert_eq!(memory_map.try_as_slice().err(), Some(StivaleError::InvalidLength));
+    /// ```
+    pub fn try_as_slice(&self) -> Result<&[StivaleMemoryMapEntry], StivaleError> {
+        unsafe { utils::checked_slice(self.entry_array.as_ptr(), self.entries_len) }
+    }
+
     /// # Safety
     /// ptr must be a pointer to a properly initialized StivaleMemoryMapTag struct with `mem_entry_count` entries in the `entry_array`
     pub unsafe fn new_from_ptr_count(ptr: *mut (), mem_entry_count: u64) -> *mut Self {
@@ -179,11 +472,12 @@ impl StivaleMemoryMapTag {
     }
 
     /// Returns an iterator over all the memory regions.
-    pub fn iter(&self) -> StivaleMemoryMapIter {
+    pub fn iter(&self) -> StivaleMemoryMapIter<'_> {
         StivaleMemoryMapIter {
             sref: self,
             current: 0x00,
-            phantom: PhantomData::default(),
+            back: self.entries_len,
+            phantom: PhantomData,
         }
     }
 }
@@ -195,6 +489,8 @@ pub struct StivaleMemoryMapIter<'a> {
     sref: &'a StivaleMemoryMapTag,
     /// The index of the memory map entry that we are about to index.
     current: u64,
+    /// The index one past the memory map entry that we are about to index from the back.
+    back: u64,
     phantom: PhantomData<&'a StivaleMemoryMapEntry>,
 }
 
@@ -202,7 +498,7 @@ impl<'a> Iterator for StivaleMemoryMapIter<'a> {
     type Item = &'a StivaleMemoryMapEntry;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current < self.sref.entries_len {
+        if self.current < self.back {
             let entry = &self.sref.as_slice()[self.current as usize];
             self.current += 1;
 
@@ -211,8 +507,27 @@ impl<'a> Iterator for StivaleMemoryMapIter<'a> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.current) as usize;
+        (len, Some(len))
+    }
 }
 
+impl<'a> DoubleEndedIterator for StivaleMemoryMapIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current < self.back {
+            self.back -= 1;
+
+            Some(&self.sref.as_slice()[self.back as usize])
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for StivaleMemoryMapIter<'a> {}
+
 /// This tag is used to get the current UNIX epoch, as per RTC.
 #[repr(C)]
 pub struct StivaleEpochTag {
@@ -223,11 +538,13 @@ pub struct StivaleEpochTag {
 
 bitflags::bitflags! {
     /// Bitfield representing the firmware and boot flags passed by the bootloader.
+    ///
+    /// Bit 0 is set if the kernel was booted in a legacy BIOS mode and unset if it was booted
+    /// in UEFI mode, use [StivaleFirmwareTag::is_uefi] and [StivaleFirmwareTag::is_bios] to
+    /// test for the firmware.
     pub struct StivaleFirmwareTagFlags: u64 {
-        /// The kernel was booted in UEFI mode.
-        const UEFI = 0x00;
         /// The kernel was booted in a legacy BIOS mode.
-        const BIOS = 0x01;
+        const BIOS = 1 << 0;
     }
 }
 
@@ -239,6 +556,47 @@ pub struct StivaleFirmwareTag {
     pub flags: StivaleFirmwareTagFlags,
 }
 
+impl StivaleFirmwareTag {
+    /// Returns a copy of the firmware and boot flags passed by the bootloader.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let tag = StivaleFirmwareTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_FIRMWARE_ID, next: 0 },
+    ///     flags: StivaleFirmwareTagFlags::BIOS,
+    /// };
+    ///
+    /// assert_eq!(tag.flags(), StivaleFirmwareTagFlags::BIOS);
+    /// ```
+    #[inline]
+    pub fn flags(&self) -> StivaleFirmwareTagFlags {
+        self.flags
+    }
+
+    /// Returns true if the kernel was booted in UEFI mode.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let header = || StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_FIRMWARE_ID, next: 0 };
+    ///
+    /// let bios = StivaleFirmwareTag { header: header(), flags: StivaleFirmwareTagFlags::from_bits_truncate(1) };
+    /// assert!(bios.is_bios() && !bios.is_uefi());
+    ///
+    /// let uefi = StivaleFirmwareTag { header: header(), flags: StivaleFirmwareTagFlags::from_bits_truncate(0) };
+    /// assert!(uefi.is_uefi() && !uefi.is_bios());
+    /// ```
+    pub fn is_uefi(&self) -> bool {
+        !self.is_bios()
+    }
+
+    /// Returns true if the kernel was booted in a legacy BIOS mode.
+    pub fn is_bios(&self) -> bool {
+        self.flags().contains(StivaleFirmwareTagFlags::BIOS)
+    }
+}
+
 /// This tag is used to get a pointer to the EFI system table if available.
 #[repr(C)]
 pub struct StivaleEfiSystemTableTag {
@@ -247,6 +605,19 @@ pub struct StivaleEfiSystemTableTag {
     pub system_table_addr: u64,
 }
 
+impl StivaleEfiSystemTableTag {
+    /// Returns the address of the EFI system table.
+    pub fn addr(&self) -> u64 {
+        self.system_table_addr
+    }
+
+    /// Returns a pointer to the EFI system table, which can be cast to the system table type of
+    /// a UEFI crate to call the runtime services.
+    pub fn as_ptr(&self) -> *const core::ffi::c_void {
+        self.addr() as *const core::ffi::c_void
+    }
+}
+
 /// This tag is used to get the kernel with a pointer to a copy the raw executable
 /// file of the kernel that the bootloader loaded.
 #[repr(C)]
@@ -265,6 +636,30 @@ pub struct StivaleKernelSlideTag {
     pub kernel_slide: u64,
 }
 
+impl StivaleKernelSlideTag {
+    /// Returns the slide that the bootloader applied over the kernel's load address.
+    pub fn slide(&self) -> u64 {
+        self.kernel_slide
+    }
+
+    /// Returns the runtime address of the provided link-time address by applying the kernel
+    /// slide to it.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let tag = StivaleKernelSlideTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_KERNEL_SLIDE_ID, next: 0 },
+    ///     kernel_slide: 0x20_0000,
+    /// };
+    ///
+    /// assert_eq!(tag.relocate(0xffffffff80001000), 0xffffffff80201000);
+    /// ```
+    pub fn relocate(&self, link_time_addr: u64) -> u64 {
+        link_time_addr.wrapping_add(self.slide())
+    }
+}
+
 /// This tag is used to get the kernel the command line string that was passed to it by
 /// the bootloader.
 #[repr(C)]
@@ -290,6 +685,12 @@ impl StivaleEdidInfoTag {
         unsafe { core::slice::from_raw_parts(self.info_array.as_ptr(), self.edid_len as usize) }
     }
 
+    /// Returns the EDID information as a rust slice, or an error if the information pointer is
+    /// null or its length is implausibly large.
+    pub fn try_as_slice(&self) -> Result<&[u8], StivaleError> {
+        unsafe { utils::checked_slice(self.info_array.as_ptr(), self.edid_len) }
+    }
+
     /// # Safety
     /// ptr must be a pointer to a properly initialized StivaleEdidInfoTag struct with `edid_count` entries in the `info_array`
     pub unsafe fn new_from_ptr_count(ptr: *mut (), edid_count: u64) -> *mut Self {
@@ -325,14 +726,28 @@ pub struct StivaleModule {
 }
 
 impl StivaleModule {
-    /// Returns the size of this module.
+    /// Returns the size of this module. Returns zero if the end address is below the start
+    /// address on a malformed entry.
     #[inline]
     pub fn size(&self) -> u64 {
-        self.end - self.start
+        self.end.saturating_sub(self.start)
     }
 
     /// Returns the ASCII 0-terminated string passed to the module as specified in the config file
-    /// as a rust string.
+    /// as a rust string. If the string is not null terminated, it spans the whole 128 byte array
+    /// and no bytes past the end of the array are read.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::StivaleModule;
+    /// let module = StivaleModule {
+    ///     start: 0,
+    ///     end: 0,
+    ///     string: [b'a'; 128],
+    /// };
+    ///
+    /// assert_eq!(module.as_str().len(), 128);
+    /// ```
     #[inline]
     pub fn as_str(&self) -> &str {
         super::utils::string_from_slice(&self.string)
@@ -362,8 +777,15 @@ impl<'a> Iterator for StivaleModuleIter<'a> {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.sref.module_len.saturating_sub(self.current) as usize;
+        (len, Some(len))
+    }
 }
 
+impl<'a> ExactSizeIterator for StivaleModuleIter<'a> {}
+
 /// This tag is used to get the modules that the bootloader loaded alongside the kernel, if any.
 #[repr(C)]
 pub struct StivaleModuleTag {
@@ -376,11 +798,11 @@ pub struct StivaleModuleTag {
 
 impl StivaleModuleTag {
     /// Returns an iterator over all the modules that were loaded.
-    pub fn iter(&self) -> StivaleModuleIter {
+    pub fn iter(&self) -> StivaleModuleIter<'_> {
         StivaleModuleIter {
             sref: self,
             current: 0,
-            phantom: PhantomData::default(),
+            phantom: PhantomData,
         }
     }
 
@@ -391,6 +813,12 @@ impl StivaleModuleTag {
         }
     }
 
+    /// Returns the modules as a rust slice, or an error if the modules pointer is null or the
+    /// amount of modules is implausibly large.
+    pub fn try_as_slice(&self) -> Result<&[StivaleModule], StivaleError> {
+        unsafe { utils::checked_slice(self.modules_array.as_ptr(), self.module_len) }
+    }
+
     /// # Safety
     /// ptr must be a pointer to a properly initialized StivaleModuleTag struct with `module_count` entries in the `modules_array`
     pub unsafe fn new_from_ptr_count(ptr: *mut (), module_count: u64) -> *mut Self {
@@ -485,14 +913,14 @@ impl StivaleSmpTag {
     /// rules in order to not trigger UB:
     ///
     /// - Writing to [`StivaleSmpInfo::goto_address`] will cause it to start executing at the
-    /// provided address as such a proper stack must have been set at
-    /// [`StivaleSmpInfo::target_stack`] already if a stack is needed.
+    ///   provided address as such a proper stack must have been set at
+    ///   [`StivaleSmpInfo::target_stack`] already if a stack is needed.
     /// - The stack pointer written to [`StivaleSmpInfo::target_stack`] must not alias already
-    /// mapped memory, this means that the memory area dedicated to the stack must be exclusively
-    /// used for the AP stack and stack overflows can trigger UB (consider using a guard page).
+    ///   mapped memory, this means that the memory area dedicated to the stack must be exclusively
+    ///   used for the AP stack and stack overflows can trigger UB (consider using a guard page).
     /// - The address pointed by [`StivaleSmpInfo::goto_address`] must be that of a
-    /// `extern "C" fn(&'static StivaleSmpInfo) -> !`, this also means that once written this
-    /// struct must not be mutated any further.
+    ///   `extern "C" fn(&'static StivaleSmpInfo) -> !`, this also means that once written this
+    ///   struct must not be mutated any further.
     pub unsafe fn as_slice_mut(&mut self) -> &mut [StivaleSmpInfo] {
         core::slice::from_raw_parts_mut(self.smp_info_array.as_mut_ptr(), self.cpu_count as usize)
     }
@@ -514,6 +942,26 @@ pub struct StivalePxeInfoTag {
     pub server_ip: u32,
 }
 
+impl StivalePxeInfoTag {
+    /// Returns the server IP as an [Ipv4Addr].
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// # use core::net::Ipv4Addr;
+    /// let tag = StivalePxeInfoTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_PXE_SERVER_INFO_ID, next: 0 },
+    ///     // The server IP is stored in network byte order.
+    ///     server_ip: 0xC0A80001u32.to_be(),
+    /// };
+    ///
+    /// assert_eq!(tag.server_ipv4(), Ipv4Addr::new(192, 168, 0, 1));
+    /// ```
+    pub fn server_ipv4(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.server_ip.to_ne_bytes())
+    }
+}
+
 /// This tag reports that there is a memory mapped UART port and its address.
 #[repr(C)]
 pub struct StivaleUartTag {
@@ -540,6 +988,62 @@ pub struct StivaleVMapTag {
     pub address: u64,
 }
 
+impl StivaleVMapTag {
+    /// Returns VMAP_HIGH, where the physical memory is mapped in the higher half.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Returns the virtual address of the provided physical address in the higher half.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let tag = StivaleVMapTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_VMAP_ID, next: 0 },
+    ///     address: 0xffff800000000000,
+    /// };
+    ///
+    /// assert_eq!(tag.phys_to_virt(0x1000), 0xffff800000001000);
+    /// assert_eq!(tag.virt_to_phys(tag.phys_to_virt(0x1000)), Some(0x1000));
+    /// assert_eq!(tag.virt_to_phys(0x1000), None);
+    /// ```
+    pub fn phys_to_virt(&self, phys: u64) -> u64 {
+        self.address().wrapping_add(phys)
+    }
+
+    /// Returns the physical address of the provided virtual address in the higher half, or
+    /// `None` if the virtual address is below VMAP_HIGH.
+    pub fn virt_to_phys(&self, virt: u64) -> Option<u64> {
+        virt.checked_sub(self.address())
+    }
+}
+
+/// This tag describes the virtual offset of the higher half direct map, where all of the physical
+/// memory is mapped. This tag supersedes the [StivaleVMapTag] and shares its identifier.
+#[repr(C)]
+pub struct StivaleHhdmTag {
+    pub header: StivaleTagHeader,
+    /// The virtual address offset of the higher half direct map.
+    pub addr: u64,
+}
+
+impl StivaleHhdmTag {
+    /// Returns the virtual address offset of the higher half direct map.
+    pub fn addr(&self) -> u64 {
+        self.addr
+    }
+
+    /// Returns the virtual address of the provided physical address in the higher half
+    /// direct map.
+    pub fn phys_to_virt(&self, phys: u64) -> u64 {
+        self.addr().wrapping_add(phys)
+    }
+}
+
+/// This tag is used to get the kernel with a pointer to a copy the raw executable
+/// file of the kernel that the bootloader loaded, along with its size. This tag supersedes
+/// the [StivaleKernelFileTag].
 #[repr(C)]
 pub struct StivaleKernelFileV2Tag {
     pub header: StivaleTagHeader,
@@ -549,6 +1053,19 @@ pub struct StivaleKernelFileV2Tag {
     pub kernel_size: u64,
 }
 
+impl StivaleKernelFileV2Tag {
+    /// Returns the raw kernel file as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.kernel_start == 0 {
+            return &[];
+        }
+
+        unsafe {
+            core::slice::from_raw_parts(self.kernel_start as *const u8, self.kernel_size as usize)
+        }
+    }
+}
+
 bitflags::bitflags! {
     pub struct StivalePmrPermissionFlags: u64 {
         const EXECUTABLE = 1 << 0;
@@ -594,9 +1111,46 @@ impl StivalePmrsTag {
     }
 }
 
+/// This tag describes the physical and virtual base addresses the kernel was loaded at.
 #[repr(C)]
 pub struct StivaleKernelBaseAddressTag {
     pub header: StivaleTagHeader,
+    /// Physical base address of the kernel.
     pub physical_base_address: u64,
+    /// Virtual base address of the kernel.
     pub virtual_base_address: u64,
 }
+
+impl StivaleKernelBaseAddressTag {
+    /// Returns the physical base address the kernel was loaded at.
+    pub fn physical_base_address(&self) -> u64 {
+        self.physical_base_address
+    }
+
+    /// Returns the virtual base address the kernel was loaded at.
+    pub fn virtual_base_address(&self) -> u64 {
+        self.virtual_base_address
+    }
+}
+
+bitflags::bitflags! {
+    /// Bitfield telling which of the GUIDs of the boot volume tag are valid.
+    pub struct StivaleBootVolumeTagFlags: u64 {
+        /// The GUID of the volume is valid.
+        const GUID_VALID      = 1 << 0;
+        /// The GUID of the partition is valid.
+        const PART_GUID_VALID = 1 << 1;
+    }
+}
+
+/// This tag describes the volume and partition the kernel was loaded from.
+#[repr(C)]
+pub struct StivaleBootVolumeTag {
+    pub header: StivaleTagHeader,
+    /// Flags telling which of the GUIDs are valid.
+    pub flags: StivaleBootVolumeTagFlags,
+    /// GUID of the volume the kernel was loaded from.
+    pub guid: Guid,
+    /// GUID of the partition the kernel was loaded from.
+    pub part_guid: Guid,
+}
@@ -0,0 +1,243 @@
+//! This module contains the helpers of the stivale2 terminal tag.
+
+use core::fmt;
+
+use super::error::StivaleError;
+use super::tag::{StivaleTerminalFlags, StivaleTerminalTag};
+
+/// The maximum length of a single terminal write, as assumed by the stivale2 specification when
+/// the bootloader does not provide one.
+const DEFAULT_MAX_LENGTH: u64 = 1024;
+
+/// Magic length passed to the terminal write function to query the size of the terminal context.
+pub const STIVALE2_TERM_CTX_SIZE: u64 = -1i64 as u64;
+/// Magic length passed to the terminal write function to save the terminal context.
+pub const STIVALE2_TERM_CTX_SAVE: u64 = -2i64 as u64;
+/// Magic length passed to the terminal write function to restore the terminal context.
+pub const STIVALE2_TERM_CTX_RESTORE: u64 = -3i64 as u64;
+
+/// Callback type for DEC private mode sequences.
+pub const STIVALE2_TERM_CB_DEC: u64 = 10;
+/// Callback type for the bell.
+pub const STIVALE2_TERM_CB_BELL: u64 = 20;
+/// Callback type for private identification requests.
+pub const STIVALE2_TERM_CB_PRIVATE_ID: u64 = 30;
+/// Callback type for status report requests.
+pub const STIVALE2_TERM_CB_STATUS_REPORT: u64 = 40;
+/// Callback type for cursor position report requests.
+pub const STIVALE2_TERM_CB_POS_REPORT: u64 = 50;
+/// Callback type for keyboard LED changes.
+pub const STIVALE2_TERM_CB_KBD_LEDS: u64 = 60;
+/// Callback type for ANSI mode sequences.
+pub const STIVALE2_TERM_CB_MODE: u64 = 70;
+/// Callback type for Linux private sequences.
+pub const STIVALE2_TERM_CB_LINUX: u64 = 80;
+
+/// Writer to the stivale terminal implementing [core::fmt::Write], which allows using the
+/// `write!` and `writeln!` macros with the terminal.
+///
+/// ## Example
+/// ```rust,no_run
+/// # fn example(stivale_struct: &stivale_boot::v2::StivaleStruct) {
+/// use core::fmt::Write;
+///
+/// let terminal_tag = stivale_struct.terminal().expect("Terminal tag was provided by the stivale2 bootloader");
+/// let mut writer = terminal_tag.writer().expect("Terminal write function was provided");
+///
+/// writeln!(writer, "x = {}", 42).unwrap();
+/// # }
+/// ```
+///
+/// ## Safety
+/// The terminal is **not** thread safe.
+#[derive(Clone, Copy)]
+pub struct StivaleTerminalWriter {
+    term_write: extern "C" fn(*const i8, u64),
+    max_length: usize,
+    wrap: Option<u16>,
+    column: u16,
+}
+
+impl StivaleTerminalWriter {
+    /// Writes the provided bytes to the terminal, split into chunks of at most the maximum
+    /// length of a single terminal write. A maximum length of zero means that the length of a
+    /// single write is unlimited.
+    pub(crate) fn write_bytes(&self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
+        if self.max_length == 0 {
+            (self.term_write)(bytes.as_ptr() as *const i8, bytes.len() as u64);
+            return;
+        }
+
+        for chunk in bytes.chunks(self.max_length) {
+            (self.term_write)(chunk.as_ptr() as *const i8, chunk.len() as u64);
+        }
+    }
+
+    /// Makes the writer track the column of the cursor and insert a newline before a character
+    /// would be written past the provided amount of columns, for bootloaders whose terminal does
+    /// not wrap lines by itself. The amount of columns of the terminal is returned by
+    /// [StivaleTerminalTag::dimensions].
+    ///
+    /// The cursor is assumed to start at the beginning of a line. Every character other than a
+    /// newline or a carriage return is assumed to advance the cursor by one column, so escape
+    /// sequences are not accounted for.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use core::fmt::Write;
+    /// # use std::sync::Mutex;
+    /// # use stivale_boot::v2::*;
+    /// static OUTPUT: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+    ///
+    /// extern "C" fn write(ptr: *const i8, len: u64) {
+    ///     let bytes = unsafe { core::slice::from_raw_parts(ptr as *const u8, len as usize) };
+    ///     OUTPUT.lock().unwrap().extend_from_slice(bytes);
+    /// }
+    ///
+    /// let mut tag: StivaleTerminalTag = unsafe { core::mem::zeroed() };
+    /// (tag.cols, tag.rows) = (4, 25);
+    /// tag.term_write_addr = write as usize as u64;
+    ///
+    /// let (cols, _rows) = tag.dimensions();
+    /// let mut writer = tag.writer().unwrap().with_wrap(cols);
+    /// write!(writer, "abcdef\nghij").unwrap();
+    /// assert_eq!(&OUTPUT.lock().unwrap()[..], b"abcd\nef\nghij");
+    /// ```
+    pub fn with_wrap(self, cols: u16) -> Self {
+        Self {
+            wrap: Some(cols),
+            column: 0,
+            ..self
+        }
+    }
+}
+
+impl fmt::Write for StivaleTerminalWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let cols = match self.wrap {
+            Some(cols) if cols > 0 => cols,
+            _ => {
+                self.write_bytes(s.as_bytes());
+                return Ok(());
+            }
+        };
+
+        let bytes = s.as_bytes();
+        let mut start = 0;
+
+        for (i, &byte) in bytes.iter().enumerate() {
+            match byte {
+                b'\n' | b'\r' => self.column = 0,
+                // UTF-8 continuation bytes do not start a new character.
+                0x80..=0xbf => {}
+                _ => {
+                    if self.column == cols {
+                        self.write_bytes(&bytes[start..i]);
+                        self.write_bytes(b"\n");
+                        start = i;
+                        self.column = 0;
+                    }
+
+                    self.column += 1;
+                }
+            }
+        }
+
+        self.write_bytes(&bytes[start..]);
+        Ok(())
+    }
+}
+
+impl StivaleTerminalTag {
+    /// Returns the flags telling which terminal features are available.
+    pub fn flags(&self) -> StivaleTerminalFlags {
+        StivaleTerminalFlags::from_bits_truncate(self.flags)
+    }
+
+    /// Returns the `(cols, rows)` dimensions of the terminal in characters.
+    pub fn dimensions(&self) -> (u16, u16) {
+        (self.cols, self.rows)
+    }
+
+    /// Returns the maximum length of a single write to the terminal. If the bootloader
+    /// does not provide it, the spec-defined default of 1024 is returned.
+    /// A maximum length of zero means that the length of a single write is unlimited.
+    pub fn max_length(&self) -> u64 {
+        if self.flags().contains(StivaleTerminalFlags::MAX_LENGTH) {
+            self.max_length
+        } else {
+            DEFAULT_MAX_LENGTH
+        }
+    }
+
+    /// Returns a writer to the stivale terminal implementing [core::fmt::Write], or `None` if
+    /// the bootloader did not provide a terminal write function.
+    pub fn writer(&self) -> Option<StivaleTerminalWriter> {
+        Some(StivaleTerminalWriter {
+            term_write: self.term_func()?,
+            max_length: self.max_length() as usize,
+            wrap: None,
+            column: 0,
+        })
+    }
+
+    /// Returns the terminal write function if the terminal supports context control.
+    fn context_control_func(&self) -> Result<extern "C" fn(*const i8, u64), StivaleError> {
+        if self.flags().contains(StivaleTerminalFlags::CONTEXT_CONTROL) {
+            self.term_func()
+                .ok_or(StivaleError::ContextControlUnavailable)
+        } else {
+            Err(StivaleError::ContextControlUnavailable)
+        }
+    }
+
+    /// Returns the size of the terminal context in bytes, or `None` if the terminal does not
+    /// support context control.
+    pub fn context_size(&self) -> Option<u64> {
+        let term_func = self.context_control_func().ok()?;
+
+        let mut size = 0u64;
+        term_func(&mut size as *mut u64 as *const i8, STIVALE2_TERM_CTX_SIZE);
+
+        Some(size)
+    }
+
+    /// Saves the terminal context into the provided buffer, which must be at least
+    /// [StivaleTerminalTag::context_size] bytes long.
+    pub fn save_context(&self, buf: &mut [u8]) -> Result<(), StivaleError> {
+        let term_func = self.check_context_buffer(buf.len())?;
+        term_func(buf.as_mut_ptr() as *const i8, STIVALE2_TERM_CTX_SAVE);
+
+        Ok(())
+    }
+
+    /// Restores the terminal context from the provided buffer, previously filled by
+    /// [StivaleTerminalTag::save_context].
+    pub fn restore_context(&self, buf: &[u8]) -> Result<(), StivaleError> {
+        let term_func = self.check_context_buffer(buf.len())?;
+        term_func(buf.as_ptr() as *const i8, STIVALE2_TERM_CTX_RESTORE);
+
+        Ok(())
+    }
+
+    /// Returns the terminal write function, or an error if context control is unavailable or if
+    /// a buffer of `len` bytes cannot hold the terminal context.
+    fn check_context_buffer(
+        &self,
+        len: usize,
+    ) -> Result<extern "C" fn(*const i8, u64), StivaleError> {
+        let size = self
+            .context_size()
+            .ok_or(StivaleError::ContextControlUnavailable)?;
+
+        if (len as u64) < size {
+            Err(StivaleError::BufferTooSmall)
+        } else {
+            self.context_control_func()
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! This module contains the helpers of the stivale2 MMIO32 UART tag.
+//!
+//! The UART is driven as a 16550 compatible UART with 32-bit wide registers, which means that
+//! register `n` is located at `address + n * 4` and is accessed using 32-bit wide reads and writes.
+//! Characters are written, zero extended, to the transmitter holding register at the reported
+//! address as required by the stivale2 specification. Received characters are read from the
+//! receiver buffer register, which shares register `0` with the transmitter holding register.
+
+use core::fmt;
+
+use super::tag::StivaleUartTag;
+
+/// The distance in bytes between two consecutive UART registers.
+pub const UART_REGISTER_STRIDE: u64 = 4;
+/// Index of the transmitter holding register.
+pub const UART_THR: u64 = 0;
+/// Index of the receiver buffer register.
+pub const UART_RBR: u64 = 0;
+/// Index of the line status register.
+pub const UART_LSR: u64 = 5;
+/// Bit of the line status register which is set when the transmitter holding register is empty.
+pub const UART_LSR_THR_EMPTY: u32 = 1 << 5;
+/// Bit of the line status register which is set when a received byte is waiting in the receiver
+/// buffer register.
+pub const UART_LSR_DATA_READY: u32 = 1 << 0;
+
+/// Writer to the UART reported by the bootloader implementing [core::fmt::Write], which
+/// provides a zero-config early serial console.
+///
+/// ## Safety
+/// The UART is **not** thread safe.
+#[derive(Clone, Copy)]
+pub struct StivaleUartWriter {
+    address: u64,
+}
+
+impl StivaleUartWriter {
+    /// Returns a pointer to the UART register with the provided index.
+    fn register(&self, index: u64) -> *mut u32 {
+        (self.address + index * UART_REGISTER_STRIDE) as *mut u32
+    }
+
+    /// Reads a single byte from the UART, or returns `None` if no byte has been received.
+    pub fn read_byte(&mut self) -> Option<u8> {
+        unsafe {
+            if self.register(UART_LSR).read_volatile() & UART_LSR_DATA_READY == 0 {
+                return None;
+            }
+
+            Some(self.register(UART_RBR).read_volatile() as u8)
+        }
+    }
+
+    /// Writes a single byte to the UART, waiting until the transmitter is ready first.
+    pub fn write_byte(&mut self, byte: u8) {
+        unsafe {
+            while self.register(UART_LSR).read_volatile() & UART_LSR_THR_EMPTY == 0 {
+                core::hint::spin_loop();
+            }
+
+            self.register(UART_THR).write_volatile(byte as u32);
+        }
+    }
+}
+
+impl fmt::Write for StivaleUartWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.write_byte(byte);
+        }
+
+        Ok(())
+    }
+}
+
+impl StivaleUartTag {
+    /// Returns a writer to the UART implementing [core::fmt::Write].
+    pub fn writer(&self) -> StivaleUartWriter {
+        StivaleUartWriter {
+            address: self.address,
+        }
+    }
+
+    /// Reads a single byte from the UART, or returns `None` if no byte has been received. The
+    /// data ready bit ([UART_LSR_DATA_READY]) of the line status register ([UART_LSR]) is checked
+    /// before reading the receiver buffer register ([UART_RBR]).
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::*;
+    /// let mut registers = [0u32; 8];
+    /// registers[UART_RBR as usize] = b'x' as u32;
+    /// let registers = registers.as_mut_ptr();
+    ///
+    /// let tag = StivaleUartTag {
+    ///     header: StivaleTagHeader { identifier: STIVALE2_STRUCT_TAG_MMIO32_UART_ID, next: 0 },
+    ///     address: registers as u64,
+    /// };
+    /// assert_eq!(tag.read_byte(), None);
+    ///
+    /// unsafe { registers.add(UART_LSR as usize).write(UART_LSR_DATA_READY) };
+    /// assert_eq!(tag.read_byte(), Some(b'x'));
+    /// ```
+    pub fn read_byte(&self) -> Option<u8> {
+        self.writer().read_byte()
+    }
+
+    /// Reads a single byte from the UART, spinning until one has been received.
+    pub fn read_byte_blocking(&self) -> u8 {
+        loop {
+            if let Some(byte) = self.read_byte() {
+                return byte;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+}
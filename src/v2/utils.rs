@@ -1,10 +1,155 @@
-/// Helper function to validate and create a string from a slice.
+use core::fmt;
+
+use super::error::StivaleError;
+
+/// The maximum size in bytes of the data of a variable length tag that is considered plausible
+/// by the checked slice accessors.
+pub(crate) const MAX_SLICE_SIZE: usize = 0x10_0000;
+
+/// Helper function to create a slice from a pointer and an element count, which returns an error
+/// if the pointer is null or the slice would be larger than [MAX_SLICE_SIZE] bytes.
+///
+/// # Safety
+/// If the checks pass, `ptr` must be valid for reads of `len` elements for `'a`.
+pub(crate) unsafe fn checked_slice<'a, T>(
+    ptr: *const T,
+    len: u64,
+) -> Result<&'a [T], StivaleError> {
+    if ptr.is_null() {
+        return Err(StivaleError::NullPointer);
+    }
+
+    let size = (len as usize).checked_mul(core::mem::size_of::<T>());
+
+    match size {
+        Some(size) if len <= usize::MAX as u64 && size <= MAX_SLICE_SIZE => {
+            Ok(core::slice::from_raw_parts(ptr, len as usize))
+        }
+        _ => Err(StivaleError::InvalidLength),
+    }
+}
+
+/// Helper function to validate and create a string from a slice. The string stops at the first
+/// null byte, or at the end of the slice if there is none. If the bytes are not valid UTF-8, the
+/// string is truncated to the longest valid prefix.
 pub(crate) fn string_from_slice(slice: &[u8]) -> &str {
+    let bytes = until_null(slice);
+
+    match core::str::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(error) => unsafe { core::str::from_utf8_unchecked(&bytes[..error.valid_up_to()]) },
+    }
+}
+
+/// Creates a string from a slice, stopping at the first null byte or at the end of the slice if
+/// there is none. Unlike the accessors of the tags, which truncate invalid strings, this returns
+/// an error if the bytes are not valid UTF-8.
+///
+/// ## Example
+/// ```rust
+/// # use stivale_boot::v2::str_from_slice_checked;
+/// assert_eq!(str_from_slice_checked(b"Limine\0garbage"), Ok("Limine"));
+/// assert!(str_from_slice_checked(b"Lim\xffine\0").is_err());
+/// ```
+pub fn str_from_slice_checked(slice: &[u8]) -> Result<&str, core::str::Utf8Error> {
+    core::str::from_utf8(until_null(slice))
+}
+
+/// Returns the bytes of the slice before the first null byte.
+fn until_null(slice: &[u8]) -> &[u8] {
+    let length = slice
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(slice.len());
+
+    &slice[..length]
+}
+
+/// Helper function to create a string from a null-terminated pointer, scanning at most `max`
+/// bytes for the null terminator. If the bytes before the terminator are not valid UTF-8, the
+/// string is truncated to the longest valid prefix.
+///
+/// # Safety
+/// `ptr` must be valid for reads of up to `max` bytes, or up to and including the null
+/// terminator if it comes first, for `'a`.
+pub(crate) unsafe fn c_str_from_ptr<'a>(ptr: *const u8, max: usize) -> &'a str {
     let mut length = 0;
 
-    while length < slice.len() && slice[length] != 0 {
+    while length < max && *ptr.add(length) != 0 {
         length += 1;
     }
 
-    unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(slice.as_ptr(), length)) }
+    let bytes = core::slice::from_raw_parts(ptr, length);
+
+    match core::str::from_utf8(bytes) {
+        Ok(string) => string,
+        Err(error) => core::str::from_utf8_unchecked(&bytes[..error.valid_up_to()]),
+    }
+}
+
+/// A GUID stored in its 16 byte binary form, as used by the stivale2 struct tags.
+///
+/// The first three fields of the GUID are stored in little endian and the last two in big
+/// endian, which is taken into account when formatting it.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guid([u8; 16]);
+
+impl Guid {
+    /// Creates a GUID from its 16 byte binary form.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the 16 byte binary form of the GUID.
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Guid {
+    /// Formats the GUID in its canonical `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` form.
+    ///
+    /// ## Example
+    /// ```rust
+    /// # use stivale_boot::v2::Guid;
+    /// let guid = Guid::from_bytes([
+    ///     0x28, 0x73, 0x2a, 0xc1, 0x1f, 0xf8, 0xd2, 0x11, 0xba, 0x4b, 0x00, 0xa0, 0xc9, 0x3e,
+    ///     0xc9, 0x3b,
+    /// ]);
+    ///
+    /// let mut buffer = [0u8; 36];
+    /// let mut cursor = &mut buffer[..];
+    /// core::fmt::Write::write_fmt(&mut Cursor(&mut cursor), format_args!("{}", guid)).unwrap();
+    /// assert_eq!(&buffer[..], b"c12a7328-f81f-11d2-ba4b-00a0c93ec93b");
+    /// # struct Cursor<'a, 'b>(&'a mut &'b mut [u8]);
+    /// # impl core::fmt::Write for Cursor<'_, '_> {
+    /// #     fn write_str(&mut self, s: &str) -> core::fmt::Result {
+    /// #         let (head, tail) = core::mem::take(self.0).split_at_mut(s.len());
+    /// #         head.copy_from_slice(s.as_bytes());
+    /// #         *self.0 = tail;
+    /// #         Ok(())
+    /// #     }
+    /// # }
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = &self.0;
+
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-",
+            u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            u16::from_le_bytes([b[4], b[5]]),
+            u16::from_le_bytes([b[6], b[7]]),
+        )?;
+
+        for (i, byte) in b[8..].iter().enumerate() {
+            if i == 2 {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
 }